@@ -19,7 +19,6 @@ fn main() {
 
     task.lock().set_text("waiting again ...");
 
-    spin_loop.clear_stream(stdout()).unwrap();
     spin_loop.spawn_stream(stdout());
 
     thread::sleep(Duration::from_secs(2));
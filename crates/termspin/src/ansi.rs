@@ -20,3 +20,147 @@ impl core::fmt::Display for ClearLine {
         f.write_str("\x1B[2K")
     }
 }
+
+/// Reset all text styling (SGR 0).
+pub struct ResetStyle;
+
+impl core::fmt::Display for ResetStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\x1B[0m")
+    }
+}
+
+/// Clear everything from the cursor to the end of the screen.
+pub struct ClearBelow;
+
+impl core::fmt::Display for ClearBelow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\x1B[0J")
+    }
+}
+
+/// Clear everything from the cursor to the end of the current line.
+pub struct ClearLineEnd;
+
+impl core::fmt::Display for ClearLineEnd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\x1B[0K")
+    }
+}
+
+/// Clear the whole screen and move the cursor to the top left corner.
+pub struct EraseDisplay;
+
+impl core::fmt::Display for EraseDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\x1B[2J\x1B[H")
+    }
+}
+
+/// Set the title of the terminal window (OSC 0).
+pub struct SetTitle<T>(pub T);
+
+impl<T: core::fmt::Display> core::fmt::Display for SetTitle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\x1B]0;{}\x07", self.0)
+    }
+}
+
+/// Report progress to the taskbar (OSC 9;4),
+/// supported by `ConEmu` and Windows Terminal among others.
+///
+/// Percentages are clamped to `100`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskbarProgress {
+    /// Remove the progress indicator.
+    Hidden,
+    /// Regular progress with the given percentage.
+    Normal(u8),
+    /// Failed progress with the given percentage.
+    Error(u8),
+    /// Progress without a known percentage.
+    Indeterminate,
+    /// Paused progress with the given percentage.
+    Paused(u8),
+}
+
+impl core::fmt::Display for TaskbarProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (state, percent) = match *self {
+            TaskbarProgress::Hidden => (0, 0),
+            TaskbarProgress::Normal(p) => (1, p),
+            TaskbarProgress::Error(p) => (2, p),
+            TaskbarProgress::Indeterminate => (3, 0),
+            TaskbarProgress::Paused(p) => (4, p),
+        };
+
+        write!(f, "\x1B]9;4;{state};{}\x07", percent.min(100))
+    }
+}
+
+/// Set the badge of the current iTerm2 session (OSC 1337),
+/// the badge is displayed in the top right corner of the session.
+///
+/// An empty badge removes it.
+#[cfg(feature = "iterm2")]
+pub struct SetBadge<T>(pub T);
+
+#[cfg(feature = "iterm2")]
+impl<T: core::fmt::Display> core::fmt::Display for SetBadge<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let badge = self.0.to_string();
+
+        f.write_str("\x1B]1337;SetBadgeFormat=")?;
+        crate::util::Base64(badge.as_bytes()).fmt(f)?;
+        f.write_str("\x07")
+    }
+}
+
+/// Display the text as a hyperlink to the given URL (OSC 8).
+///
+/// Control characters in the URL are dropped, terminals
+/// without hyperlink support display the text only.
+pub struct Hyperlink<'u, T>(pub &'u str, pub T);
+
+impl<T: core::fmt::Display> core::fmt::Display for Hyperlink<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\x1B]8;;")?;
+        for c in self.0.chars().filter(|c| !c.is_control()) {
+            core::fmt::Write::write_char(f, c)?;
+        }
+        f.write_str("\x1B\\")?;
+        self.1.fmt(f)?;
+        f.write_str("\x1B]8;;\x1B\\")
+    }
+}
+
+/// Enable or disable focus reporting (mode 1004).
+///
+/// While enabled, the terminal sends `ESC [ I` to the input
+/// when it gains focus and `ESC [ O` when it loses focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FocusReporting(pub bool);
+
+impl core::fmt::Display for FocusReporting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 {
+            f.write_str("\x1B[?1004h")
+        } else {
+            f.write_str("\x1B[?1004l")
+        }
+    }
+}
+
+/// Show or hide the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CursorVisible(pub bool);
+
+impl core::fmt::Display for CursorVisible {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 {
+            f.write_str("\x1B[?25h")
+        } else {
+            f.write_str("\x1B[?25l")
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Allocation counting for auditing the render path.
+//!
+//! Spinners often run for hours, so displaying and advancing
+//! frames should not allocate on every tick. Install
+//! [`CountingAllocator`] as the global allocator to verify this.
+//!
+//! # Example
+//!
+//! ```
+//! use termspin::{audit::{self, CountingAllocator}, progress::Progress, spinner, Group, Line};
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator::system();
+//!
+//! let mut group = Group::new();
+//! group
+//!     .push(Line::new(spinner::dots()).with_text("downloading"))
+//!     .push(Line::new(spinner::dots()).with_text("extracting").with_wrap_width(Some(8)))
+//!     .push(Line::new(Progress::new().with_total(Some(10))).with_text("copying"));
+//!
+//! assert_eq!(audit::count_tick(&mut group), 0);
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Write,
+};
+
+use crate::{util::DisplayFn, Frames};
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A global allocator that counts the allocations
+/// made by each thread.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingAllocator<A = System> {
+    inner: A,
+}
+
+impl CountingAllocator {
+    /// Count the allocations of the system allocator.
+    #[must_use]
+    pub const fn system() -> Self {
+        Self { inner: System }
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Count the allocations of the given allocator.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+// SAFETY: all calls are forwarded to the inner allocator.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record();
+        self.inner.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record();
+        self.inner.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record();
+        self.inner.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+    }
+}
+
+fn record() {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+/// The amount of allocations made by the current thread
+/// so far, always zero unless [`CountingAllocator`] is
+/// the global allocator.
+#[must_use]
+pub fn allocations() -> u64 {
+    ALLOCATIONS.try_with(Cell::get).unwrap_or_default()
+}
+
+/// The amount of allocations made by the current thread
+/// while running the given function.
+pub fn count(f: impl FnOnce()) -> u64 {
+    let before = allocations();
+    f();
+    allocations() - before
+}
+
+/// The amount of allocations made by the current thread
+/// during a single tick of the given frames, i.e. while
+/// clearing, displaying and advancing them.
+pub fn count_tick(frames: &mut dyn Frames) -> u64 {
+    count(|| {
+        let mut sink = Sink;
+        let _ = write!(sink, "{}", DisplayFn::new(|f| frames.clear(f)));
+        let _ = write!(sink, "{frames}");
+        frames.advance();
+    })
+}
+
+/// Discards everything written to it.
+struct Sink;
+
+impl Write for Sink {
+    fn write_str(&mut self, _s: &str) -> std::fmt::Result {
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::{io, time::Duration};
+
+use crate::{
+    spinner, term::TermMode, FlushPolicy, Frames, Group, Line, Loop, RestartPolicy, SharedFrames,
+};
+
+/// A builder that constructs a tree of groups and lines
+/// in one expression, see [`Group::builder`].
+///
+/// Lines added with [`GroupBuilder::shared_line`] are returned
+/// as handles by [`GroupBuilder::build_with_handles`] in the order
+/// they were added, including the ones of nested groups.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, FrameState, Group, Line};
+/// let (group, handles) = Group::builder()
+///     .header("release")
+///     .shared_line(Line::new(spinner::dots()).with_text("build"))
+///     .nested(|tests| {
+///         tests
+///             .header("tests")
+///             .shared_line(Line::new(spinner::dots()).with_text("unit"))
+///     })
+///     .build_with_handles();
+///
+/// let [build, unit] = &handles[..] else {
+///     unreachable!()
+/// };
+/// build.lock().set_state(FrameState::Done);
+/// unit.lock().set_text("unit 3/10");
+///
+/// assert_eq!(group.len(), 3);
+/// ```
+#[must_use]
+#[derive(Default)]
+pub struct GroupBuilder {
+    group: Group,
+    handles: Vec<SharedFrames<Line>>,
+}
+
+impl GroupBuilder {
+    /// Create a builder for an empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a line without a spinner.
+    pub fn header(self, text: &str) -> Self {
+        self.line(
+            Line::new(spinner::empty())
+                .with_spinner_visible(false)
+                .with_text(text),
+        )
+    }
+
+    /// Add a line.
+    pub fn line(self, line: Line) -> Self {
+        self.frames(line)
+    }
+
+    /// Add a line and keep a handle to it.
+    pub fn shared_line(mut self, line: Line) -> Self {
+        let line = line.shared();
+        self.handles.push(line.clone());
+        self.frames(line)
+    }
+
+    /// Add any frames.
+    pub fn frames(mut self, frames: impl Frames) -> Self {
+        self.group.push(frames);
+        self
+    }
+
+    /// Add a group that is indented one level
+    /// deeper, built by the given function.
+    pub fn nested(mut self, f: impl FnOnce(GroupBuilder) -> GroupBuilder) -> Self {
+        let nested = f(GroupBuilder::new());
+        let (group, handles) = nested.build_with_handles();
+        self.handles.extend(handles);
+        self.frames(group.with_indent(1))
+    }
+
+    /// Configure the group being built, e.g. to
+    /// change its options.
+    pub fn configure(mut self, f: impl FnOnce(Group) -> Group) -> Self {
+        self.group = f(self.group);
+        self
+    }
+
+    /// Build the group.
+    pub fn build(self) -> Group {
+        self.group
+    }
+
+    /// Build the group and return the handles to the lines
+    /// added with [`GroupBuilder::shared_line`].
+    pub fn build_with_handles(self) -> (Group, Vec<SharedFrames<Line>>) {
+        (self.group, self.handles)
+    }
+}
+
+/// A builder that configures a [`Loop`] before it is
+/// shared or started, see [`Loop::builder`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use termspin::{spinner, FlushPolicy, Line, Loop};
+/// # use std::time::Duration;
+/// let l = Loop::builder(Line::new(spinner::dots()).with_text("loading"))
+///     .interval(Duration::from_millis(80))
+///     .hide_cursor(true)
+///     .tty_detection(true)
+///     .flush_policy(FlushPolicy::EveryFrame)
+///     .output(std::io::stderr())
+///     .spawn();
+///
+/// l.stop();
+/// ```
+#[must_use]
+pub struct LoopBuilder<F: Frames> {
+    frames: F,
+    interval: Duration,
+    output: Option<Box<dyn io::Write + Send>>,
+    auto_stop: bool,
+    hide_cursor: bool,
+    tty_detection: bool,
+    restart_policy: RestartPolicy,
+    flush_policy: FlushPolicy,
+}
+
+impl<F: Frames> LoopBuilder<F> {
+    /// Create a builder for a loop of the given frames.
+    pub fn new(frames: F) -> Self {
+        Self {
+            frames,
+            interval: Duration::from_millis(100),
+            output: None,
+            auto_stop: true,
+            hide_cursor: false,
+            tty_detection: false,
+            restart_policy: RestartPolicy::default(),
+            flush_policy: FlushPolicy::default(),
+        }
+    }
+
+    /// Set the interval of the loop, defaults to 100 milliseconds.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the stream used by [`LoopBuilder::spawn`],
+    /// defaults to the standard output.
+    pub fn output(mut self, output: impl io::Write + Send + 'static) -> Self {
+        self.output = Some(Box::new(output));
+        self
+    }
+
+    /// See [`Loop::auto_stop`].
+    pub fn auto_stop(mut self, stop: bool) -> Self {
+        self.auto_stop = stop;
+        self
+    }
+
+    /// See [`Loop::hide_cursor`].
+    pub fn hide_cursor(mut self, hide: bool) -> Self {
+        self.hide_cursor = hide;
+        self
+    }
+
+    /// Set the term mode of the loop with [`TermMode::detect`],
+    /// defaults to `false`.
+    pub fn tty_detection(mut self, detect: bool) -> Self {
+        self.tty_detection = detect;
+        self
+    }
+
+    /// See [`Loop::restart_policy`].
+    pub fn restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = policy;
+        self
+    }
+
+    /// See [`Loop::flush_policy`].
+    pub fn flush_policy(mut self, policy: FlushPolicy) -> Self {
+        self.flush_policy = policy;
+        self
+    }
+
+    /// Build the loop without starting it,
+    /// the output is not used.
+    pub fn build(self) -> Loop<F> {
+        self.build_with_output().0
+    }
+
+    /// Build the loop and spawn it on a separate thread
+    /// writing to the output, see [`Loop::spawn_stream`].
+    pub fn spawn(self) -> Loop<F> {
+        let (l, output) = self.build_with_output();
+        match output {
+            Some(output) => l.spawn_stream(output),
+            None => l.spawn_stream(io::stdout()),
+        }
+        l
+    }
+
+    fn build_with_output(self) -> (Loop<F>, Option<Box<dyn io::Write + Send>>) {
+        let l = Loop::new(self.interval, self.frames);
+        l.auto_stop(self.auto_stop);
+        l.hide_cursor(self.hide_cursor);
+        l.restart_policy(self.restart_policy);
+        l.flush_policy(self.flush_policy);
+        if self.tty_detection {
+            l.term_mode(TermMode::detect());
+        }
+        (l, self.output)
+    }
+}
+
+impl<F: Frames + core::fmt::Debug> core::fmt::Debug for LoopBuilder<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoopBuilder")
+            .field("frames", &self.frames)
+            .field("interval", &self.interval)
+            .field("auto_stop", &self.auto_stop)
+            .field("hide_cursor", &self.hide_cursor)
+            .field("tty_detection", &self.tty_detection)
+            .field("restart_policy", &self.restart_policy)
+            .field("flush_policy", &self.flush_policy)
+            .finish_non_exhaustive()
+    }
+}
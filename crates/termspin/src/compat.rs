@@ -0,0 +1,209 @@
+//! A thin facade resembling the API of `indicatif`,
+//! easing the migration of code that uses it.
+//!
+//! The types are backed by [`Line`], [`Group`] and [`Loop`],
+//! and are drawn to the standard error.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use termspin::compat::{MultiProgress, ProgressBar};
+//! let multi = MultiProgress::new();
+//! let download = multi.add(ProgressBar::new(1024));
+//! download.set_message("downloading");
+//!
+//! for _ in 0..1024 {
+//!     download.inc(1);
+//! }
+//!
+//! download.finish_with_message("downloaded");
+//! ```
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crate::{progress::Progress, spinner, FrameState, Frames, Group, Line, Loop, SharedFrames};
+
+/// The interval the bars are redrawn at.
+const INTERVAL: Duration = Duration::from_millis(100);
+
+/// A progress bar or spinner with a message.
+///
+/// The bar is drawn on its own until it is finished
+/// or added to a [`MultiProgress`]. Clones refer to the same bar.
+#[derive(Clone)]
+pub struct ProgressBar {
+    progress: SharedFrames<Progress>,
+    line: SharedFrames<Line>,
+    draw: Arc<Mutex<Option<Loop<SharedFrames<Line>>>>>,
+}
+
+impl ProgressBar {
+    /// Create and start drawing a bar with the given length.
+    #[must_use]
+    pub fn new(len: u64) -> Self {
+        let progress = Progress::new().with_total(Some(len)).shared();
+        Self::draw(progress.clone(), Line::new(progress))
+    }
+
+    /// Create and start drawing a spinner without a known length.
+    #[must_use]
+    pub fn new_spinner() -> Self {
+        Self::draw(Progress::new().shared(), Line::new(spinner::dots()))
+    }
+
+    fn draw(progress: SharedFrames<Progress>, line: Line) -> Self {
+        let line = line.shared();
+        let draw = Loop::new(INTERVAL, line.clone());
+        draw.spawn_stream(io::stderr());
+
+        Self {
+            progress,
+            line,
+            draw: Arc::new(Mutex::new(Some(draw))),
+        }
+    }
+
+    /// Set the message displayed after the bar.
+    pub fn set_message(&self, msg: &str) {
+        self.line.lock().set_text(msg);
+    }
+
+    /// Advance the position by the given amount.
+    pub fn inc(&self, delta: u64) {
+        self.progress.lock().inc(delta);
+    }
+
+    /// Set the position.
+    pub fn set_position(&self, pos: u64) {
+        self.progress.lock().set_position(pos);
+    }
+
+    /// The current position.
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.progress.lock().position()
+    }
+
+    /// Set the length.
+    pub fn set_length(&self, len: u64) {
+        self.progress.lock().set_total(Some(len));
+    }
+
+    /// The length if known.
+    #[must_use]
+    pub fn length(&self) -> Option<u64> {
+        self.progress.lock().total()
+    }
+
+    /// Whether the bar was finished.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.line.lock().state().is_finished()
+    }
+
+    /// Complete the bar and leave it displayed.
+    pub fn finish(&self) {
+        {
+            let mut progress = self.progress.lock();
+            if let Some(total) = progress.total() {
+                progress.set_position(total);
+            }
+        }
+        self.line.lock().set_state(FrameState::Done);
+        self.stop_drawing(true);
+    }
+
+    /// Complete the bar with the given message
+    /// and leave it displayed.
+    pub fn finish_with_message(&self, msg: &str) {
+        self.set_message(msg);
+        self.finish();
+    }
+
+    /// Complete the bar and remove it.
+    ///
+    /// Bars added to a [`MultiProgress`] stay in it.
+    pub fn finish_and_clear(&self) {
+        self.line.lock().set_state(FrameState::Done);
+        self.stop_drawing(false);
+    }
+
+    /// Stop drawing the bar on its own, optionally
+    /// printing its final state.
+    fn stop_drawing(&self, keep: bool) {
+        let Some(draw) = self.draw.lock().unwrap().take() else {
+            return;
+        };
+
+        // The loop exits once its last handle is dropped.
+        let handle = draw.handle();
+        drop(draw);
+        while handle.exists() {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let line = self.line.lock();
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "{}", line.clear());
+        if keep {
+            let _ = writeln!(stderr, "{}", *line);
+        }
+    }
+}
+
+/// Multiple bars drawn together as a [`Group`].
+///
+/// The bars are drawn until the last clone
+/// of the `MultiProgress` is dropped.
+#[derive(Clone)]
+pub struct MultiProgress {
+    group: SharedFrames<Group>,
+    _draw: Loop<SharedFrames<Group>>,
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultiProgress {
+    /// Create and start drawing an empty set of bars.
+    #[must_use]
+    pub fn new() -> Self {
+        let group = Group::new().shared();
+        let draw = Loop::new(INTERVAL, group.clone());
+        draw.spawn_stream(io::stderr());
+
+        Self { group, _draw: draw }
+    }
+
+    /// Add a bar after the existing ones.
+    ///
+    /// The bar is no longer drawn on its own.
+    #[must_use]
+    pub fn add(&self, pb: ProgressBar) -> ProgressBar {
+        pb.stop_drawing(false);
+        self.group.lock().push(pb.line.clone());
+        pb
+    }
+
+    /// Add a bar at the given position, or after
+    /// the existing ones if it is out of bounds.
+    ///
+    /// The bar is no longer drawn on its own.
+    #[must_use]
+    pub fn insert(&self, idx: usize, pb: ProgressBar) -> ProgressBar {
+        pb.stop_drawing(false);
+        let mut group = self.group.lock();
+        let idx = idx.min(group.len());
+        group.insert(idx, pb.line.clone());
+        drop(group);
+        pb
+    }
+}
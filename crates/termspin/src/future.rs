@@ -0,0 +1,28 @@
+use std::future::Future;
+
+use crate::{scoped::Spinning, Line};
+
+/// Display the line on the standard error while the future runs.
+///
+/// The line is finished as done or failed depending on the
+/// result of the future and is left displayed, it is also
+/// finished as failed if the future is dropped before completing.
+/// No executor is required, the line is drawn on a separate thread.
+///
+/// # Example
+///
+/// ```no_run
+/// # use termspin::{spin_while, spinner, Line};
+/// # async fn fetch() -> std::io::Result<String> { Ok(String::new()) }
+/// # async fn run() -> std::io::Result<()> {
+/// let body = spin_while(Line::new(spinner::dots()).with_text("fetching"), fetch()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[allow(clippy::missing_errors_doc)]
+pub async fn spin_while<T, E>(line: Line, fut: impl Future<Output = Result<T, E>>) -> Result<T, E> {
+    let spinning = Spinning::start(line);
+    let res = fut.await;
+    spinning.finish(res.is_ok());
+    res
+}
@@ -2,21 +2,60 @@
 #![warn(clippy::pedantic, missing_docs)]
 
 use std::{
+    any::Any,
     borrow::Cow,
+    collections::hash_map::DefaultHasher,
     fmt::{Display, Write},
+    hash::{Hash, Hasher},
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
 };
 
-use ansi::{ClearLine, CursorUp};
+use ansi::{ClearBelow, ClearLine, ClearLineEnd, CursorUp, Hyperlink, ResetStyle};
 use downcast::AnySync;
-use util::DisplayFn;
+use util::{
+    char_width, display_width, single_line, Cadence, DisplayFn, Indented, Sanitized, Token, Tokens,
+    WidthCounter,
+};
 
 pub mod ansi;
+#[cfg(feature = "alloc-audit")]
+pub mod audit;
+mod builder;
+pub mod compat;
+#[cfg(feature = "async")]
+mod future;
+mod log_tail;
 mod loops;
+pub mod progress;
+mod report;
+mod scoped;
 pub mod spinner;
+pub mod term;
+pub mod text;
+pub mod time;
+#[cfg(all(feature = "timerfd", target_os = "linux"))]
+mod timer;
+mod typed_group;
 mod util;
 
-pub use loops::Loop;
-pub use util::SharedFrames;
+pub use builder::{GroupBuilder, LoopBuilder};
+#[cfg(feature = "async")]
+pub use future::spin_while;
+pub use log_tail::LogTail;
+pub use loops::{
+    BackgroundPolicy, FlushPolicy, Loop, LoopHandle, LoopRegistry, RenderMode, RestartPolicy,
+    StalledTick, StatusExport, StatusReport,
+};
+pub use report::{Report, ReportEntry};
+pub use scoped::with_spinner;
+#[cfg(all(feature = "timerfd", target_os = "linux"))]
+pub use timer::TimerFd;
+pub use typed_group::TypedGroup;
+pub use util::{NullStream, SharedFrames};
 
 /// Frames that can be printed to the terminal via
 /// [`fmt::Display`](core::fmt::Display).
@@ -26,9 +65,103 @@ pub trait Frames: AnySync + core::fmt::Display {
     /// Advance to the next frame.
     fn advance(&mut self);
 
+    /// Advance to the next frame at the given time.
+    ///
+    /// Time-aware frames, e.g. elapsed time or rates, use the
+    /// given time instead of the current one, and containers
+    /// pass it on to their children. Loops advance their frames
+    /// at the time of the tick, so a single clock drives all of
+    /// them. This also allows driving frames with a fake clock
+    /// in tests.
+    ///
+    /// Defaults to [`Frames::advance`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{time, Frames, TypedGroup};
+    /// # use std::time::{Duration, Instant};
+    /// let mut group = TypedGroup::new();
+    /// group.push(time::elapsed());
+    ///
+    /// group.advance_at(Instant::now() + Duration::from_secs(63));
+    /// assert_eq!(group.to_string(), "1m03s");
+    /// ```
+    fn advance_at(&mut self, now: Instant) {
+        let _ = now;
+        self.advance();
+    }
+
     /// Reset to the first frame.
     fn reset(&mut self) {}
 
+    /// Append the displayed frames to the buffer.
+    ///
+    /// Loops render into a single buffer that is reused between
+    /// ticks, containers override this to render their children
+    /// into it directly instead of through nested formatters.
+    ///
+    /// Defaults to [`Display`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Frames, Group, Line};
+    /// let mut group = Group::new();
+    /// group.push(Line::new(spinner::empty()).with_text("a"));
+    ///
+    /// let mut buf = String::new();
+    /// group.render(&mut buf);
+    /// assert_eq!(buf, group.to_string());
+    /// ```
+    fn render(&self, buf: &mut String) {
+        let _ = write!(buf, "{self}");
+    }
+
+    /// Append the frames to the buffer over their output
+    /// of the previous tick, which the cursor was moved to
+    /// the start of with [`Frames::rewind`].
+    ///
+    /// Containers only move the cursor past children that are
+    /// not [dirty](Frames::is_dirty) instead of printing them again.
+    ///
+    /// Defaults to [`Frames::render`].
+    fn repaint(&self, buf: &mut String) {
+        self.render(buf);
+    }
+
+    /// Whether the displayed output changed since
+    /// [`Frames::mark_clean`] was last called.
+    ///
+    /// Loops skip ticks that change nothing, and containers
+    /// skip repainting children that are not dirty. Frames
+    /// that cannot tell are always dirty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Frames, Line};
+    /// let mut line = Line::new(spinner::empty()).with_text("waiting");
+    /// assert!(line.is_dirty());
+    ///
+    /// line.mark_clean();
+    /// assert!(!line.is_dirty());
+    ///
+    /// line.set_text("done");
+    /// assert!(line.is_dirty());
+    /// ```
+    fn is_dirty(&self) -> bool {
+        true
+    }
+
+    /// Remember the displayed output for [`Frames::is_dirty`],
+    /// called right after the frames are rendered for display.
+    ///
+    /// Frames that track what they displayed, e.g. for
+    /// [`Frames::displayed_lines`], should also record it here
+    /// instead of as a side effect of formatting.
+    fn mark_clean(&mut self) {}
+
     /// Write ANSI codes to the given formatter
     /// that clears the printed output.
     #[allow(clippy::missing_errors_doc)]
@@ -36,29 +169,205 @@ pub trait Frames: AnySync + core::fmt::Display {
         Ok(())
     }
 
+    /// Write ANSI codes to the given formatter that move
+    /// the cursor to the start of the printed output
+    /// without clearing it, so that it can be overwritten.
+    ///
+    /// Defaults to [`Frames::clear`].
+    #[allow(clippy::missing_errors_doc)]
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.clear(f)
+    }
+
     /// The amount of lines that is supposed to be printed
     /// and cleared.
     ///
+    /// Multiple lines are separated by new lines,
+    /// the last line should still not end with one.
+    ///
     /// This is required by groups.
     fn lines(&self) -> usize {
         0
     }
 
+    /// The total amount of lines occupied by the printed
+    /// output, including lines that are cleared by
+    /// [`Frames::clear`] itself.
+    ///
+    /// Defaults to [`Frames::lines`].
+    fn total_lines(&self) -> usize {
+        self.lines()
+    }
+
+    /// The amount of lines occupied by the frame
+    /// when it was last displayed.
+    ///
+    /// Containers clear children based on this value, frames
+    /// whose [`Frames::lines`] can change after being displayed
+    /// should track it.
+    ///
+    /// Defaults to [`Frames::lines`].
+    fn displayed_lines(&self) -> usize {
+        self.lines()
+    }
+
+    /// Whether [`Frames::lines`] changed since the frame
+    /// was last displayed.
+    fn lines_changed(&self) -> bool {
+        self.lines() != self.displayed_lines()
+    }
+
+    /// The amount of distinct frames before the frames
+    /// start repeating, if known.
+    ///
+    /// This is required by combinators that play
+    /// frames a given amount of times.
+    fn frame_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// The state of the work the frames represent.
+    ///
+    /// Containers use this to reason about their children,
+    /// e.g. for sorting or summaries.
+    fn state(&self) -> FrameState {
+        FrameState::Running
+    }
+
+    /// Whether the work the frames represent is over.
+    ///
+    /// Defaults to whether the [state](Frames::state) is finished.
+    fn is_finished(&self) -> bool {
+        self.state().is_finished()
+    }
+
+    /// The completed fraction of the work the frames
+    /// represent between `0.0` and `1.0`, if known.
+    ///
+    /// This is used for reporting progress outside of
+    /// the printed frames, e.g. in the window title.
+    fn progress(&self) -> Option<f64> {
+        None
+    }
+
     /// The length of the printed text if known
     /// in advance.
     fn print_len(&self) -> Option<usize> {
         None
     }
+
+    /// The preferred interval between advances, if any.
+    ///
+    /// Containers advance children that have one on their
+    /// own cadence, and children without one on every tick.
+    fn interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// A plain description of the work the frames represent,
+    /// if any, e.g. the text of a [`Line`].
+    ///
+    /// This is used for announcing state changes,
+    /// see [`Loop::announce`].
+    fn label(&self) -> Option<String> {
+        None
+    }
+
+    /// Call the given function with each child
+    /// if the frames are a container.
+    fn for_each_child(&self, _f: &mut dyn FnMut(&dyn Frames)) {}
 }
 downcast::downcast_sync!(dyn Frames);
 
+/// The state of the work represented by [`Frames`].
+///
+/// The variants are ordered by how much attention
+/// they usually require.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FrameState {
+    /// The work failed.
+    Failed,
+    /// The work is in progress.
+    #[default]
+    Running,
+    /// The work is paused.
+    Paused,
+    /// The work is done.
+    Done,
+}
+
+impl FrameState {
+    /// Whether the work is either done or failed.
+    #[must_use]
+    pub fn is_finished(self) -> bool {
+        matches!(self, FrameState::Done | FrameState::Failed)
+    }
+}
+
+/// The styles of a [`Line`] spinner for each [`FrameState`].
+///
+/// By default failed spinners are red, running ones are cyan,
+/// paused ones are dimmed and done ones are green.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateStyles {
+    /// The style of failed spinners.
+    pub failed: text::Style,
+    /// The style of running spinners.
+    pub running: text::Style,
+    /// The style of paused spinners.
+    pub paused: text::Style,
+    /// The style of done spinners.
+    pub done: text::Style,
+}
+
+impl Default for StateStyles {
+    fn default() -> Self {
+        Self {
+            failed: text::Style::new().with_fg(text::Color::Red),
+            running: text::Style::new().with_fg(text::Color::Cyan),
+            paused: text::Style::new().with_dim(true),
+            done: text::Style::new().with_fg(text::Color::Green),
+        }
+    }
+}
+
+impl StateStyles {
+    /// The style for the given state.
+    pub fn get(&self, state: FrameState) -> text::Style {
+        match state {
+            FrameState::Failed => self.failed,
+            FrameState::Running => self.running,
+            FrameState::Paused => self.paused,
+            FrameState::Done => self.done,
+        }
+    }
+}
+
 /// A stateful group of displayable frames
 /// that are separated by new lines.
 #[must_use]
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Group {
     indent: usize,
+    fast_clear: bool,
+    panic_isolation: bool,
+    parallel_advance: bool,
+    auto_remove_finished: bool,
+    align_spinners: bool,
     frames: Vec<Box<dyn Frames>>,
+    persisted: Vec<String>,
+    /// Placeholders for children that panicked during display.
+    panicked: Mutex<Vec<(usize, Line)>>,
+    /// Recorded timings while profiling.
+    timings: Option<Mutex<Vec<ChildTimings>>>,
+    /// The threads for parallel advances, started on first use.
+    advance_pool: Option<Mutex<AdvancePool>>,
+    cadence: Cadence,
+    events: Option<EventSender>,
+    /// The fingerprint of the children when they were marked clean.
+    clean: Option<u64>,
 }
 
 impl Group {
@@ -67,6 +376,54 @@ impl Group {
         Self::default()
     }
 
+    /// Create a new empty group with space
+    /// for at least the given amount of items.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            frames: Vec::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Create a builder that constructs a group
+    /// with its children in one expression.
+    pub fn builder() -> GroupBuilder {
+        GroupBuilder::new()
+    }
+
+    /// A summary of the labeled children and their
+    /// states, see [`Report`].
+    pub fn report(&self) -> Report {
+        Report::new(self)
+    }
+
+    /// The amount of items the group can hold
+    /// without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.frames.capacity()
+    }
+
+    /// Reserve space for at least the given amount
+    /// of additional items.
+    pub fn reserve(&mut self, additional: usize) -> &mut Self {
+        self.frames.reserve(additional);
+        self
+    }
+
+    /// Remove all items after the given length.
+    /// No-op if the group is not longer than that.
+    pub fn truncate(&mut self, len: usize) -> &mut Self {
+        if let Some(events) = &mut self.events {
+            for idx in (len..self.frames.len()).rev() {
+                events.removed(idx);
+            }
+        }
+
+        self.frames.truncate(len);
+        self
+    }
+
     /// The amount of items in this group.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -85,14 +442,17 @@ impl Group {
     ///
     /// Panics if `index > len`.
     pub fn insert(&mut self, idx: usize, frames: impl Frames) -> &mut Self {
+        if let Some(events) = &mut self.events {
+            events.added(idx, frames.state());
+        }
+
         self.frames.insert(idx, Box::new(frames));
         self
     }
 
     /// Push an item at the end.
     pub fn push(&mut self, frames: impl Frames) -> &mut Self {
-        self.frames.push(Box::new(frames));
-        self
+        self.insert(self.frames.len(), frames)
     }
 
     /// Extend this group from an iterator.
@@ -107,10 +467,55 @@ impl Group {
         self
     }
 
+    /// Extend this group with clones of the given shared frames.
+    pub fn extend_from_shared<F: Frames>(&mut self, shared: &[SharedFrames<F>]) -> &mut Self {
+        self.reserve(shared.len());
+        self.extend(shared.iter().cloned())
+    }
+
     /// Remove an item at the given position.
     /// No-op if the position is invalid.
     pub fn remove(&mut self, idx: usize) -> &mut Self {
-        self.frames.remove(idx);
+        self.try_remove(idx);
+        self
+    }
+
+    /// Remove an item at the given position and return it,
+    /// or `None` if the position is invalid.
+    pub fn try_remove(&mut self, idx: usize) -> Option<Box<dyn Frames>> {
+        if idx >= self.frames.len() {
+            return None;
+        }
+
+        if let Some(events) = &mut self.events {
+            events.removed(idx);
+        }
+
+        Some(self.frames.remove(idx))
+    }
+
+    /// Remove an item at the given position and print
+    /// its current content permanently above the frames
+    /// of this group.
+    /// No-op if the position is invalid.
+    ///
+    /// The content is printed on the next display of
+    /// the group and is not cleared afterwards,
+    /// so this is intended for the outermost group
+    /// that is driven by a [`Loop`].
+    pub fn persist_child(&mut self, idx: usize) -> &mut Self {
+        let Some(child) = self.try_remove(idx) else {
+            return self;
+        };
+
+        let align = self.spinner_alignment();
+        let mut persisted = DisplayFn::new(|f| self.fmt_child(align, &*child, f)).to_string();
+
+        if !persisted.is_empty() && !persisted.ends_with('\n') {
+            persisted.push('\n');
+        }
+
+        self.persisted.push(persisted);
         self
     }
 
@@ -124,9 +529,209 @@ impl Group {
         self.frames.iter_mut().map(|s| &mut **s)
     }
 
+    /// Return an iterator of the frames in this group
+    /// that are of the given type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Group, Line};
+    /// let mut group = Group::new();
+    /// group.push(Line::new(spinner::dots()).with_text("a"));
+    /// group.push(spinner::dots());
+    ///
+    /// assert_eq!(group.iter_as::<Line>().count(), 1);
+    /// ```
+    pub fn iter_as<T: Frames>(&self) -> impl Iterator<Item = &T> + '_ {
+        self.frames
+            .iter()
+            .filter_map(|s| s.downcast_ref::<T>().ok())
+    }
+
+    /// Return an iterator of the frames in this group
+    /// that are of the given type.
+    pub fn iter_as_mut<T: Frames>(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.frames
+            .iter_mut()
+            .filter_map(|s| s.downcast_mut::<T>().ok())
+    }
+
+    /// Return an iterator of the frames in this group
+    /// that are [`SharedFrames`] of the given type.
+    pub fn iter_shared_as<T: Frames>(&self) -> impl Iterator<Item = &SharedFrames<T>> + '_ {
+        self.iter_as::<SharedFrames<T>>()
+    }
+
     /// Retains only the items specified by the predicate.
-    pub fn retain(&mut self, f: impl Fn(&dyn Frames) -> bool) {
-        self.frames.retain(move |s| f(&**s));
+    pub fn retain(&mut self, mut f: impl FnMut(&mut dyn Frames) -> bool) {
+        let events = &mut self.events;
+        let mut idx = 0;
+
+        self.frames.retain_mut(|s| {
+            let keep = f(&mut **s);
+
+            if keep {
+                idx += 1;
+            } else if let Some(events) = events {
+                events.removed(idx);
+            }
+
+            keep
+        });
+    }
+
+    /// Remove all items specified by the predicate
+    /// and return them in their original order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, FrameState, Frames, Group, Line};
+    /// let mut group = Group::new();
+    /// group.push(Line::new(spinner::dots()).with_state(FrameState::Done));
+    /// group.push(Line::new(spinner::dots()));
+    ///
+    /// let done = group.extract_if(|s| s.state() == FrameState::Done);
+    ///
+    /// assert_eq!(done.len(), 1);
+    /// assert_eq!(group.len(), 1);
+    /// ```
+    pub fn extract_if(
+        &mut self,
+        mut f: impl FnMut(&mut dyn Frames) -> bool,
+    ) -> Vec<Box<dyn Frames>> {
+        let mut extracted = Vec::new();
+        let mut idx = 0;
+
+        while idx < self.frames.len() {
+            if f(&mut *self.frames[idx]) {
+                if let Some(events) = &mut self.events {
+                    events.removed(idx);
+                }
+                extracted.push(self.frames.remove(idx));
+            } else {
+                idx += 1;
+            }
+        }
+
+        extracted
+    }
+
+    /// The amount of items in the given state.
+    #[must_use]
+    pub fn count_state(&self, state: FrameState) -> usize {
+        self.frames.iter().filter(|s| s.state() == state).count()
+    }
+
+    /// Sort the items by their state in the order of [`FrameState`]
+    /// while keeping the order of items in the same state.
+    pub fn sort_by_state(&mut self) -> &mut Self {
+        let Some(events) = &mut self.events else {
+            self.frames.sort_by_key(|s| s.state());
+            return self;
+        };
+
+        // The last seen states have to move with the children.
+        let mut children: Vec<_> = self.frames.drain(..).zip(events.states.drain(..)).collect();
+        children.sort_by_key(|(s, _)| s.state());
+        (self.frames, events.states) = children.into_iter().unzip();
+
+        self
+    }
+
+    /// Set every line of the group that is still running
+    /// to the state returned by the given function,
+    /// including lines of nested and shared groups.
+    ///
+    /// Paused and finished lines are left as they are.
+    /// Shared children are locked without the global shared
+    /// lock, so this can be called while it is held.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, FrameState, Frames, Group, Line};
+    /// let mut group = Group::new();
+    /// group.push(Line::new(spinner::dots()).with_text("build"));
+    /// group.push(Line::new(spinner::dots()).with_text("test"));
+    /// group.push(Line::new(spinner::dots()).with_state(FrameState::Paused));
+    ///
+    /// group.finish_all_with(|line| {
+    ///     if line.text() == "build" {
+    ///         FrameState::Failed
+    ///     } else {
+    ///         FrameState::Done
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(group.count_state(FrameState::Failed), 1);
+    /// assert_eq!(group.count_state(FrameState::Done), 1);
+    /// assert_eq!(group.count_state(FrameState::Paused), 1);
+    /// ```
+    pub fn finish_all_with(&mut self, mut f: impl FnMut(&Line) -> FrameState) -> &mut Self {
+        fn finish(frames: &mut dyn Frames, f: &mut dyn FnMut(&Line) -> FrameState) {
+            if let Ok(line) = frames.downcast_mut::<Line>() {
+                if line.state == FrameState::Running {
+                    let state = f(line);
+                    line.set_state(state);
+                }
+            } else if let Ok(shared) = frames.downcast_mut::<SharedFrames<Line>>() {
+                finish(&mut *shared.lock_inner(), f);
+            } else if let Ok(group) = frames.downcast_mut::<Group>() {
+                group.finish_all_with(f);
+            } else if let Ok(shared) = frames.downcast_mut::<SharedFrames<Group>>() {
+                shared.lock_inner().finish_all_with(f);
+            }
+        }
+
+        for spinner in &mut self.frames {
+            finish(&mut **spinner, &mut f);
+        }
+        self
+    }
+
+    /// Set every line of the group that is still running
+    /// to [`FrameState::Done`], see [`Group::finish_all_with`].
+    pub fn finish_all_success(&mut self) -> &mut Self {
+        self.finish_all_with(|_| FrameState::Done)
+    }
+
+    /// Start sending events about the children of the group
+    /// to the returned receiver, replacing any previous one.
+    ///
+    /// Finished children are detected when the group is advanced,
+    /// events stop once the receiver is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, FrameState, Frames, Group, GroupEvent, Line};
+    /// let mut group = Group::new();
+    /// let events = group.events();
+    ///
+    /// group.push(Line::new(spinner::dots()));
+    /// group.iter_as_mut::<Line>().for_each(|line| {
+    ///     line.set_state(FrameState::Done);
+    /// });
+    /// group.advance();
+    ///
+    /// assert_eq!(
+    ///     events.try_iter().collect::<Vec<_>>(),
+    ///     [
+    ///         GroupEvent::ChildAdded { index: 0 },
+    ///         GroupEvent::ChildFinished { index: 0, state: FrameState::Done },
+    ///     ]
+    /// );
+    /// ```
+    pub fn events(&mut self) -> mpsc::Receiver<GroupEvent> {
+        let (sender, receiver) = mpsc::channel();
+
+        self.events = Some(EventSender {
+            sender,
+            states: self.frames.iter().map(|s| s.state()).collect(),
+        });
+
+        receiver
     }
 
     /// Print ANSI codes that clears the frames displayed
@@ -162,86 +767,1318 @@ impl Group {
         self
     }
 
-    /// Turn this group into [`SharedFrames`].
-    pub fn shared(self) -> SharedFrames<Self> {
-        SharedFrames::new(self)
+    /// Toggle clearing all lines of the group at once,
+    /// instead of clearing each child separately.
+    ///
+    /// This reduces the output for large groups, but requires
+    /// [`Frames::total_lines`] to be correct for all children.
+    pub fn with_fast_clear(mut self, fast: bool) -> Self {
+        self.fast_clear = fast;
+        self
     }
-}
-
-impl core::fmt::Display for Group {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for spinner in &self.frames {
-            if spinner.lines() > 0 {
-                for _ in 0..self.indent {
-                    "  ".fmt(f)?;
-                }
-            }
-            spinner.fmt(f)?;
-            for _ in 0..spinner.lines() {
-                '\n'.fmt(f)?;
-            }
-        }
 
-        Ok(())
+    /// Toggle clearing all lines of the group at once,
+    /// instead of clearing each child separately.
+    ///
+    /// This reduces the output for large groups, but requires
+    /// [`Frames::total_lines`] to be correct for all children.
+    pub fn set_fast_clear(&mut self, fast: bool) -> &mut Self {
+        self.fast_clear = fast;
+        self
     }
-}
 
-impl Frames for Group {
-    fn advance(&mut self) {
-        for spinner in &mut self.frames {
-            spinner.advance();
-        }
+    /// Toggle catching panics of children, disabled by default.
+    ///
+    /// Children that panic while being displayed or advanced are
+    /// replaced with a line containing the panic message, so that
+    /// a single faulty child does not bring down the whole loop.
+    ///
+    /// Children are rendered into a temporary buffer while enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{Frames, Group, Line, Loop};
+    /// # use std::time::{Duration, Instant};
+    /// struct Faulty;
+    ///
+    /// impl core::fmt::Display for Faulty {
+    ///     fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    ///         panic!("faulty frame")
+    ///     }
+    /// }
+    ///
+    /// impl Frames for Faulty {
+    ///     fn advance(&mut self) {}
+    /// }
+    ///
+    /// let mut group = Group::new().with_panic_isolation(true);
+    /// group.push(Line::new(Faulty)).push(Line::new(Faulty).shared());
+    ///
+    /// let l = Loop::new(Duration::from_millis(100), group);
+    /// let mut out = Vec::new();
+    /// let start = Instant::now();
+    /// for i in 0..3 {
+    ///     l.poll(&mut out, start + Duration::from_secs(i)).unwrap();
+    /// }
+    ///
+    /// let out = String::from_utf8(out).unwrap();
+    /// assert!(out.ends_with("error: faulty frame\x1B[0m\nerror: faulty frame\x1B[0m\n"));
+    /// ```
+    pub fn with_panic_isolation(mut self, isolate: bool) -> Self {
+        self.panic_isolation = isolate;
+        self
     }
 
-    fn reset(&mut self) {
-        for spinner in &mut self.frames {
-            spinner.reset();
-        }
+    /// Toggle catching panics of children, disabled by default.
+    ///
+    /// Children that panic while being displayed or advanced are
+    /// replaced with a line containing the panic message, so that
+    /// a single faulty child does not bring down the whole loop.
+    ///
+    /// Children are rendered into a temporary buffer while enabled.
+    pub fn set_panic_isolation(&mut self, isolate: bool) -> &mut Self {
+        self.panic_isolation = isolate;
+        self
     }
 
-    fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
-        for spinner in self.frames.iter().rev() {
-            CursorUp(spinner.lines()).fmt(f)?;
-            spinner.clear(f)?;
-        }
-
-        Ok(())
+    /// Toggle advancing children on multiple threads,
+    /// disabled by default.
+    ///
+    /// A thread for each available core is started on the first
+    /// parallel advance and kept until the group is dropped.
+    /// Each thread advances at least 256 children, so smaller
+    /// groups are still advanced on the current thread. Handing
+    /// children to the threads costs a few allocations and wake ups
+    /// per tick, which only pays off for groups with thousands of
+    /// children or children that are expensive to advance.
+    /// Rendering still happens in order on a single thread.
+    pub fn with_parallel_advance(mut self, parallel: bool) -> Self {
+        self.parallel_advance = parallel;
+        self
     }
-}
 
-/// A single line with a spinner and text.
+    /// Toggle advancing children on multiple threads,
+    /// disabled by default.
+    ///
+    /// A thread for each available core is started on the first
+    /// parallel advance and kept until the group is dropped.
+    /// Each thread advances at least 256 children, so smaller
+    /// groups are still advanced on the current thread. Handing
+    /// children to the threads costs a few allocations and wake ups
+    /// per tick, which only pays off for groups with thousands of
+    /// children or children that are expensive to advance.
+    /// Rendering still happens in order on a single thread.
+    pub fn set_parallel_advance(&mut self, parallel: bool) -> &mut Self {
+        self.parallel_advance = parallel;
+        self
+    }
+
+    /// Toggle removing finished children when the group
+    /// is advanced, disabled by default.
+    ///
+    /// Children are displayed in their finished state once
+    /// before they are removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, FrameState, Frames, Group, Line};
+    /// let mut group = Group::new().with_auto_remove_finished(true);
+    /// group.push(Line::new(spinner::dots()).with_state(FrameState::Done));
+    /// group.push(Line::new(spinner::dots()));
+    ///
+    /// group.advance();
+    /// assert_eq!(group.len(), 1);
+    /// ```
+    pub fn with_auto_remove_finished(mut self, remove: bool) -> Self {
+        self.auto_remove_finished = remove;
+        self
+    }
+
+    /// Toggle removing finished children when the group
+    /// is advanced, disabled by default.
+    ///
+    /// Children are displayed in their finished state once
+    /// before they are removed.
+    pub fn set_auto_remove_finished(&mut self, remove: bool) -> &mut Self {
+        self.auto_remove_finished = remove;
+        self
+    }
+
+    /// Toggle padding the spinners of [`Line`] children to the
+    /// widest one, so that their texts start in the same column,
+    /// disabled by default.
+    ///
+    /// This allows mixing spinners of different widths, e.g.
+    /// [`spinner::dots`] and [`spinner::earth`]. The widths are
+    /// measured whenever the group is displayed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Group, Line};
+    /// let mut group = Group::new().with_align_spinners(true);
+    /// group
+    ///     .push(Line::new(spinner::dots()).with_style_reset(false).with_text("a"))
+    ///     .push(Line::new(spinner::earth()).with_style_reset(false).with_text("b"));
+    ///
+    /// assert_eq!(group.to_string(), "⠋  a\n🌍 b\n");
+    /// ```
+    pub fn with_align_spinners(mut self, align: bool) -> Self {
+        self.set_align_spinners(align);
+        self
+    }
+
+    /// Toggle padding the spinners of [`Line`] children to the
+    /// widest one, disabled by default.
+    ///
+    /// See [`Group::with_align_spinners`].
+    pub fn set_align_spinners(&mut self, align: bool) -> &mut Self {
+        self.align_spinners = align;
+        self
+    }
+
+    /// A hash of the children and the options
+    /// that affect how they are displayed.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.indent.hash(&mut hasher);
+        self.spinner_alignment().hash(&mut hasher);
+        for spinner in &self.frames {
+            std::ptr::from_ref::<dyn Frames>(&**spinner)
+                .cast::<()>()
+                .hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// The width to pad the spinners of lines and shared lines to,
+    /// zero if spinners are not aligned.
+    fn spinner_alignment(&self) -> usize {
+        if !self.align_spinners {
+            return 0;
+        }
+
+        self.frames
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, spinner)| {
+                self.isolated(idx, || {
+                    with_line(&**spinner, |line| {
+                        line.spinner_printed().then(|| line.measure_spinner())
+                    })
+                })
+                .flatten()
+                .flatten()
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Toggle recording the time spent advancing and
+    /// displaying each child, disabled by default.
+    ///
+    /// See [`Group::timings`] and [`Group::slowest`].
+    pub fn with_profiling(mut self, profiling: bool) -> Self {
+        self.set_timings_enabled(profiling);
+        self
+    }
+
+    /// Toggle recording the time spent advancing and
+    /// displaying each child, disabled by default.
+    ///
+    /// See [`Group::timings`] and [`Group::slowest`].
+    pub fn set_profiling(&mut self, profiling: bool) -> &mut Self {
+        self.set_timings_enabled(profiling);
+        self
+    }
+
+    /// The time spent on each child since profiling was enabled
+    /// or the timings were reset.
+    ///
+    /// Timings are tracked by the position of the children,
+    /// moving children around will mix up their timings.
+    #[must_use]
+    pub fn timings(&self) -> Vec<ChildTimings> {
+        self.timings
+            .as_ref()
+            .map(|timings| {
+                timings
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The timings of at most `n` children that took the most time,
+    /// the slowest first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Group, Frames};
+    /// let mut group = Group::new().with_profiling(true);
+    /// group.push(spinner::dots());
+    /// group.push(spinner::dots());
+    ///
+    /// let _ = group.to_string();
+    /// group.advance();
+    ///
+    /// for timings in group.slowest(1) {
+    ///     eprintln!("child {} took {:?}", timings.index, timings.total());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn slowest(&self, n: usize) -> Vec<ChildTimings> {
+        let mut timings = self.timings();
+        timings.sort_by_key(|t| std::cmp::Reverse(t.total()));
+        timings.truncate(n);
+        timings
+    }
+
+    /// Discard the recorded timings.
+    pub fn reset_timings(&mut self) -> &mut Self {
+        if let Some(timings) = &mut self.timings {
+            timings
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+        self
+    }
+
+    fn set_timings_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.timings = None;
+        } else if self.timings.is_none() {
+            self.timings = Some(Mutex::default());
+        }
+    }
+
+    /// Turn this group into [`SharedFrames`].
+    pub fn shared(self) -> SharedFrames<Self> {
+        SharedFrames::new(self)
+    }
+
+    fn fmt_timed(
+        &self,
+        idx: usize,
+        align: usize,
+        spinner: &dyn Frames,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let Some(timings) = &self.timings else {
+            return self.fmt_untimed(idx, align, spinner, f);
+        };
+
+        let start = Instant::now();
+        let res = self.fmt_untimed(idx, align, spinner, f);
+        let elapsed = start.elapsed();
+
+        let mut timings = timings.lock().unwrap_or_else(PoisonError::into_inner);
+        child_timings(&mut timings, idx).render += elapsed;
+
+        res
+    }
+
+    fn fmt_untimed(
+        &self,
+        idx: usize,
+        align: usize,
+        spinner: &dyn Frames,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        if self.panic_isolation {
+            self.fmt_isolated(idx, align, spinner, f)
+        } else {
+            self.fmt_child(align, spinner, f)
+        }
+    }
+
+    fn fmt_isolated(
+        &self,
+        idx: usize,
+        align: usize,
+        spinner: &dyn Frames,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let rendered = panic::catch_unwind(AssertUnwindSafe(|| {
+            DisplayFn::new(|f| self.fmt_child(align, spinner, f)).to_string()
+        }));
+
+        match rendered {
+            Ok(rendered) => f.write_str(&rendered),
+            Err(payload) => {
+                let placeholder = panicked_line(&*payload);
+                self.fmt_child(align, &placeholder, f)?;
+                self.panicked
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push((idx, placeholder));
+                Ok(())
+            }
+        }
+    }
+
+    fn fmt_child(
+        &self,
+        align: usize,
+        spinner: &dyn Frames,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        fmt_child(self.indent, align, spinner, f)
+    }
+}
+
+/// An event about the children of a [`Group`],
+/// see [`Group::events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupEvent {
+    /// A child was added at the given position.
+    ChildAdded {
+        /// The position of the child.
+        index: usize,
+    },
+    /// The child at the given position was removed.
+    ChildRemoved {
+        /// The position of the child before it was removed.
+        index: usize,
+    },
+    /// The child at the given position finished.
+    ChildFinished {
+        /// The position of the child.
+        index: usize,
+        /// The state of the child, either done or failed.
+        state: FrameState,
+    },
+}
+
+/// Sends the events of a group and keeps track of the
+/// last seen states of the children to detect finished ones.
+struct EventSender {
+    sender: mpsc::Sender<GroupEvent>,
+    states: Vec<FrameState>,
+}
+
+impl EventSender {
+    fn added(&mut self, index: usize, state: FrameState) {
+        self.states.insert(index, state);
+        let _ = self.sender.send(GroupEvent::ChildAdded { index });
+    }
+
+    fn removed(&mut self, index: usize) {
+        self.states.remove(index);
+        let _ = self.sender.send(GroupEvent::ChildRemoved { index });
+    }
+
+    /// Send events for children that finished since the last call,
+    /// returns `false` if the receiver was dropped.
+    fn finished(&mut self, frames: &[Box<dyn Frames>]) -> bool {
+        for (index, (last, child)) in self.states.iter_mut().zip(frames).enumerate() {
+            let state = child.state();
+            let was_finished = last.is_finished();
+            *last = state;
+
+            if state.is_finished()
+                && !was_finished
+                && self
+                    .sender
+                    .send(GroupEvent::ChildFinished { index, state })
+                    .is_err()
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Time spent on a child of a [`Group`] with profiling enabled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChildTimings {
+    /// The position of the child in the group.
+    pub index: usize,
+    /// The total time spent advancing the child.
+    pub advance: Duration,
+    /// The total time spent displaying the child.
+    pub render: Duration,
+    /// The amount of times the child was advanced.
+    pub frames: u64,
+}
+
+impl ChildTimings {
+    /// The total time spent advancing and displaying the child.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.advance + self.render
+    }
+}
+
+/// The timings of the child at the given position,
+/// added if it does not exist yet.
+fn child_timings(timings: &mut Vec<ChildTimings>, idx: usize) -> &mut ChildTimings {
+    while timings.len() <= idx {
+        timings.push(ChildTimings {
+            index: timings.len(),
+            ..ChildTimings::default()
+        });
+    }
+
+    &mut timings[idx]
+}
+
+/// Add the time spent advancing a child of a group
+/// to its timings while profiling.
+fn record_advance(timings: &mut Option<Mutex<Vec<ChildTimings>>>, idx: usize, elapsed: Duration) {
+    if let Some(timings) = timings {
+        let timings = child_timings(timings.get_mut().unwrap(), idx);
+        timings.advance += elapsed;
+        timings.frames += 1;
+    }
+}
+
+/// Advance a child of a group, replacing it if it panics
+/// and panics are isolated.
+///
+/// Returns the time spent if it is measured.
+fn advance_child(
+    isolate: bool,
+    measure: bool,
+    spinner: &mut Box<dyn Frames>,
+    now: Option<Instant>,
+) -> Duration {
+    let start = measure.then(Instant::now);
+    let mut advance = || match now {
+        Some(now) => spinner.advance_at(now),
+        None => spinner.advance(),
+    };
+
+    if !isolate {
+        advance();
+    } else if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(advance)) {
+        *spinner = Box::new(panicked_line(&*payload));
+    }
+
+    start.map(|start| start.elapsed()).unwrap_or_default()
+}
+
+/// Run a function with a child of a group if it is
+/// a line or a shared line.
+fn with_line<R>(frames: &dyn Frames, f: impl FnOnce(&Line) -> R) -> Option<R> {
+    if let Ok(line) = frames.downcast_ref::<Line>() {
+        Some(f(line))
+    } else if let Ok(shared) = frames.downcast_ref::<SharedFrames<Line>>() {
+        // Only the line is locked, the global shared lock
+        // can already be held by the loop.
+        Some(f(&shared.lock_inner()))
+    } else {
+        None
+    }
+}
+
+/// Write a child of a group with the given indentation level,
+/// padding the spinner of a line to the given width.
+fn fmt_child(
+    indent: usize,
+    align: usize,
+    spinner: &dyn Frames,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    if spinner.lines() == 0 {
+        return spinner.fmt(f);
+    }
+
+    for _ in 0..indent {
+        "  ".fmt(f)?;
+    }
+    let child = DisplayFn::new(|f| {
+        if align > 0 {
+            if let Some(res) = with_line(spinner, |line| line.fmt_aligned(align, f)) {
+                return res;
+            }
+        }
+        spinner.fmt(f)
+    });
+    write!(Indented::new(f, indent), "{child}")?;
+    '\n'.fmt(f)
+}
+
+impl core::fmt::Display for Group {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for persisted in &self.persisted {
+            persisted.fmt(f)?;
+        }
+
+        let align = self.spinner_alignment();
+        for (idx, spinner) in self.frames.iter().enumerate() {
+            self.fmt_timed(idx, align, &**spinner, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Group {
+    /// Replace the children that panicked while
+    /// being displayed with their placeholders.
+    fn replace_panicked(&mut self) {
+        let panicked = self
+            .panicked
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        for (idx, placeholder) in panicked.drain(..) {
+            if let Some(spinner) = self.frames.get_mut(idx) {
+                *spinner = Box::new(placeholder);
+            }
+        }
+    }
+
+    /// Call the function with a child, catching a panic if
+    /// panics are isolated and recording a placeholder
+    /// for the child at the given position.
+    fn isolated<R>(&self, idx: usize, f: impl FnOnce() -> R) -> Option<R> {
+        if !self.panic_isolation {
+            return Some(f());
+        }
+
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(res) => Some(res),
+            Err(payload) => {
+                self.panicked
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push((idx, panicked_line(&*payload)));
+                None
+            }
+        }
+    }
+
+    /// Advance the children, at the given time if any.
+    fn advance_children(&mut self, now: Option<Instant>) {
+        // Persisted content has been displayed by now.
+        self.persisted.clear();
+
+        self.replace_panicked();
+
+        if let Some(events) = &mut self.events {
+            if !events.finished(&self.frames) {
+                self.events = None;
+            }
+        }
+
+        if self.auto_remove_finished {
+            self.retain(|spinner| !spinner.is_finished());
+        }
+
+        let isolate = self.panic_isolation;
+        let profiling = self.timings.is_some();
+        let is_due = self.cadence.tick(now.unwrap_or_else(Instant::now));
+
+        let threads = self.frames.len() / MIN_PARALLEL_CHILDREN;
+        let pool = if self.parallel_advance && threads > 1 {
+            let pool = self
+                .advance_pool
+                .get_or_insert_with(|| Mutex::new(AdvancePool::new()))
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner);
+            Some(pool).filter(|pool| !pool.workers.is_empty())
+        } else {
+            None
+        };
+
+        let Some(pool) = pool else {
+            for (idx, spinner) in self.frames.iter_mut().enumerate() {
+                if is_due(spinner.interval()) {
+                    let elapsed = advance_child(isolate, profiling, spinner, now);
+                    record_advance(&mut self.timings, idx, elapsed);
+                }
+            }
+            return;
+        };
+
+        let due: Vec<bool> = self.frames.iter().map(|c| is_due(c.interval())).collect();
+
+        let job = |children, due| AdvanceJob {
+            children,
+            due,
+            now,
+            isolate,
+            profiling,
+            elapsed: Vec::new(),
+            panic: None,
+        };
+
+        // The workers advance the leading chunks of children
+        // while the current thread advances the last one.
+        let len = self.frames.len();
+        let workers = pool.workers.len().min(threads - 1);
+        let chunk_size = len.div_ceil(workers + 1);
+        let mut children = std::mem::replace(&mut self.frames, Vec::with_capacity(len));
+        let mut due = due;
+        for worker in &pool.workers[..workers] {
+            let rest = children.split_off(chunk_size.min(children.len()));
+            let rest_due = due.split_off(chunk_size.min(due.len()));
+            let chunk = job(
+                std::mem::replace(&mut children, rest),
+                std::mem::replace(&mut due, rest_due),
+            );
+            worker.jobs.send(chunk).expect("the advance worker stopped");
+        }
+
+        let mut last = job(children, due);
+        last.run();
+
+        let mut payload = None;
+        let mut elapsed = Vec::with_capacity(len);
+        for worker in &pool.workers[..workers] {
+            let done = worker.done.recv().expect("the advance worker stopped");
+            self.frames.extend(done.children);
+            elapsed.extend(done.elapsed);
+            payload = payload.or(done.panic);
+        }
+        self.frames.extend(last.children);
+        elapsed.extend(last.elapsed);
+
+        // Children are back in the group before the panic continues.
+        if let Some(payload) = payload.or(last.panic) {
+            panic::resume_unwind(payload);
+        }
+
+        for (idx, elapsed) in elapsed.into_iter().enumerate() {
+            if let Some(elapsed) = elapsed {
+                record_advance(&mut self.timings, idx, elapsed);
+            }
+        }
+    }
+}
+
+/// The least amount of children each thread advances in parallel,
+/// fewer are not worth handing off to another thread.
+const MIN_PARALLEL_CHILDREN: usize = 256;
+
+/// Threads that advance children of a group in parallel,
+/// kept for the lifetime of the group.
+struct AdvancePool {
+    workers: Vec<AdvanceWorker>,
+}
+
+struct AdvanceWorker {
+    jobs: mpsc::Sender<AdvanceJob>,
+    done: mpsc::Receiver<AdvanceJob>,
+}
+
+impl AdvancePool {
+    /// Start a worker for each available core
+    /// except the current thread.
+    fn new() -> Self {
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+
+        let workers = (1..threads)
+            .map(|_| {
+                let (jobs, job_receiver) = mpsc::channel::<AdvanceJob>();
+                let (done_sender, done) = mpsc::channel();
+
+                // The worker exits once the group is dropped.
+                thread::spawn(move || {
+                    for mut job in job_receiver {
+                        job.run();
+                        if done_sender.send(job).is_err() {
+                            break;
+                        }
+                    }
+                });
+
+                AdvanceWorker { jobs, done }
+            })
+            .collect();
+
+        Self { workers }
+    }
+}
+
+/// Children of a group handed to a worker,
+/// sent back once they are advanced.
+struct AdvanceJob {
+    children: Vec<Box<dyn Frames>>,
+    due: Vec<bool>,
+    now: Option<Instant>,
+    isolate: bool,
+    profiling: bool,
+    /// The time spent advancing each due child.
+    elapsed: Vec<Option<Duration>>,
+    /// A panic to continue once the children are back in the group.
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl AdvanceJob {
+    fn run(&mut self) {
+        let Self {
+            children,
+            due,
+            now,
+            isolate,
+            profiling,
+            elapsed,
+            panic: payload,
+        } = self;
+
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            for (child, &due) in children.iter_mut().zip(due.iter()) {
+                elapsed.push(due.then(|| advance_child(*isolate, *profiling, child, *now)));
+            }
+        }));
+        *payload = res.err();
+    }
+}
+
+impl Frames for Group {
+    fn advance(&mut self) {
+        self.advance_children(None);
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.advance_children(Some(now));
+    }
+
+    fn reset(&mut self) {
+        self.cadence.reset();
+        for spinner in &mut self.frames {
+            spinner.reset();
+        }
+    }
+
+    fn render(&self, buf: &mut String) {
+        // Profiled and isolated children go through the formatter.
+        if self.timings.is_some() || self.panic_isolation {
+            let _ = write!(buf, "{self}");
+            return;
+        }
+
+        for persisted in &self.persisted {
+            buf.push_str(persisted);
+        }
+        let align = self.spinner_alignment();
+        for spinner in &self.frames {
+            render_child(self.indent, align, &**spinner, buf);
+        }
+    }
+
+    fn repaint(&self, buf: &mut String) {
+        if self.timings.is_some()
+            || self.panic_isolation
+            || !self.persisted.is_empty()
+            || self.clean != Some(self.fingerprint())
+        {
+            self.render(buf);
+            return;
+        }
+
+        let align = self.spinner_alignment();
+        for spinner in &self.frames {
+            repaint_child(self.indent, align, &**spinner, buf);
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        !self.persisted.is_empty()
+            || self.clean != Some(self.fingerprint())
+            || self
+                .frames
+                .iter()
+                .enumerate()
+                .any(|(idx, s)| self.isolated(idx, || s.is_dirty()).unwrap_or(true))
+    }
+
+    fn mark_clean(&mut self) {
+        let align = self.spinner_alignment();
+        // Children that panicked while displayed are not
+        // called again, they could panic outside of isolation.
+        self.replace_panicked();
+
+        let isolate = self.panic_isolation;
+        for spinner in &mut self.frames {
+            let mark_clean = AssertUnwindSafe(|| mark_clean_child(align, &mut **spinner));
+            if !isolate {
+                mark_clean();
+            } else if let Err(payload) = panic::catch_unwind(mark_clean) {
+                *spinner = Box::new(panicked_line(&*payload));
+            }
+        }
+        self.clean = Some(self.fingerprint());
+    }
+
+    fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.fast_clear && !self.lines_changed() {
+            CursorUp(self.total_lines()).fmt(f)?;
+            "\r".fmt(f)?;
+            return ClearBelow.fmt(f);
+        }
+
+        for spinner in self.frames.iter().rev() {
+            CursorUp(spinner.displayed_lines()).fmt(f)?;
+            spinner.clear(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.fast_clear && !self.lines_changed() {
+            CursorUp(self.total_lines()).fmt(f)?;
+            return "\r".fmt(f);
+        }
+
+        for spinner in self.frames.iter().rev() {
+            CursorUp(spinner.displayed_lines()).fmt(f)?;
+            spinner.rewind(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn total_lines(&self) -> usize {
+        self.frames.iter().map(|s| s.total_lines()).sum()
+    }
+
+    fn lines_changed(&self) -> bool {
+        self.frames.iter().any(|s| s.lines_changed())
+    }
+
+    /// The most important state of the children
+    /// according to the order of [`FrameState`].
+    ///
+    /// An empty group is running.
+    fn state(&self) -> FrameState {
+        self.frames
+            .iter()
+            .map(|s| s.state())
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// The average progress of the children
+    /// that report any.
+    fn progress(&self) -> Option<f64> {
+        average_progress(self.frames.iter().map(|s| s.progress()))
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        shortest_interval(self.frames.iter().map(|s| s.interval()))
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&dyn Frames)) {
+        for spinner in &self.frames {
+            f(&**spinner);
+        }
+    }
+}
+
+/// The average of the known progress values.
+#[allow(clippy::cast_precision_loss)]
+fn average_progress(progress: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    let (sum, count) = progress
+        .flatten()
+        .fold((0.0, 0_usize), |(sum, count), p| (sum + p, count + 1));
+
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// The shortest of the preferred intervals of the children
+/// of a container, `None` if any of them has none.
+fn shortest_interval(intervals: impl Iterator<Item = Option<Duration>>) -> Option<Duration> {
+    let mut shortest: Option<Duration> = None;
+
+    for interval in intervals {
+        let interval = interval?;
+        shortest = Some(shortest.map_or(interval, |shortest| shortest.min(interval)));
+    }
+
+    shortest
+}
+
+/// Mark a child of a group clean, with the spinner
+/// of a line padded to the given width.
+fn mark_clean_child(align: usize, spinner: &mut dyn Frames) {
+    if let Ok(line) = spinner.downcast_mut::<Line>() {
+        line.mark_clean_aligned(align);
+    } else if let Ok(shared) = spinner.downcast_mut::<SharedFrames<Line>>() {
+        shared.lock_inner().mark_clean_aligned(align);
+    } else {
+        spinner.mark_clean();
+    }
+}
+
+/// Append a child to the buffer in the same
+/// way as [`fmt_child`].
+fn render_child(indent: usize, align: usize, spinner: &dyn Frames, buf: &mut String) {
+    if indent > 0 || align > 0 {
+        let child = DisplayFn::new(|f| fmt_child(indent, align, spinner, f));
+        let _ = write!(buf, "{child}");
+        return;
+    }
+
+    spinner.render(buf);
+    if spinner.lines() > 0 {
+        buf.push('\n');
+    }
+}
+
+/// Append a child over its previous output in the same way
+/// as [`render_child`], only moving past it if it is clean.
+fn repaint_child(indent: usize, align: usize, spinner: &dyn Frames, buf: &mut String) {
+    let rows = spinner.total_lines();
+
+    if rows > 0 && !spinner.is_dirty() && !spinner.lines_changed() {
+        for _ in 0..rows {
+            buf.push('\n');
+        }
+    } else if indent == 0 && align == 0 {
+        spinner.repaint(buf);
+        if spinner.lines() > 0 {
+            buf.push('\n');
+        }
+    } else {
+        render_child(indent, align, spinner, buf);
+    }
+}
+
+/// A line that replaces a child that panicked.
+fn panicked_line(payload: &(dyn std::any::Any + Send)) -> Line {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown error");
+
+    Line::new(spinner::empty()).with_text(&format!("error: {message}"))
+}
+
+/// A single line with a spinner and text.
 #[must_use]
 pub struct Line {
     show_spinner: bool,
+    /// The minimum width of the spinner.
+    spinner_width: Option<usize>,
+    reset_style: bool,
+    sanitize: bool,
+    wrap_width: Option<usize>,
+    max_width: Option<usize>,
+    link: Option<Cow<'static, str>>,
+    state: FrameState,
+    /// The spinner styles for each state.
+    state_styles: Option<StateStyles>,
+    /// The width of the last row when the line was marked clean.
+    printed_width: usize,
+    /// The amount of rows when the line was marked clean.
+    printed_lines: Option<usize>,
     spinner: Box<dyn Frames>,
     text: Cow<'static, str>,
+    /// The text shortened to the maximum width,
+    /// and the width it was shortened to.
+    truncated: Option<(usize, String)>,
+    /// A short annotation displayed after the text.
+    badge: Option<Cow<'static, str>>,
+    badge_style: text::Style,
+    /// User metadata for correlating the line with other data.
+    tag: Option<Box<dyn Any + Send + Sync>>,
+    /// The fingerprint of the line when it was marked clean.
+    clean: Option<u64>,
+    started: Instant,
+    /// The time the line was set to a finished state.
+    finished: Option<Instant>,
+    duration_format: Option<time::DurationFormat>,
+    /// Renders the line instead once it is finished.
+    finish_fn: Option<FinishFn>,
 }
 
-impl Line {
-    /// Create a new line with the given spinner.
-    pub fn new(spinner: impl Frames) -> Self {
-        Self {
-            spinner: Box::new(spinner),
-            show_spinner: true,
-            text: Cow::Borrowed(""),
-        }
+type FinishFn = Box<dyn Fn(&Line) -> String + Send + Sync>;
+
+impl Line {
+    /// Create a new line with the given spinner.
+    pub fn new(spinner: impl Frames) -> Self {
+        Self {
+            spinner: Box::new(spinner),
+            show_spinner: true,
+            spinner_width: None,
+            reset_style: true,
+            sanitize: true,
+            wrap_width: None,
+            max_width: None,
+            link: None,
+            state: FrameState::Running,
+            state_styles: None,
+            printed_width: 0,
+            printed_lines: None,
+            text: Cow::Borrowed(""),
+            truncated: None,
+            badge: None,
+            badge_style: text::Style::new().with_fg(text::Color::Yellow),
+            tag: None,
+            clean: None,
+            started: Instant::now(),
+            finished: None,
+            duration_format: None,
+            finish_fn: None,
+        }
+    }
+
+    /// Get text that is displayed.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Set the text that is displayed.
+    ///
+    /// New lines in the text start new rows that are
+    /// indented to start under the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Frames, Line};
+    /// let mut line = Line::new(spinner::empty()).with_spinner_visible(false);
+    /// line.set_text("compiling\nwarning: unused variable");
+    ///
+    /// assert_eq!(line.lines(), 2);
+    /// ```
+    pub fn set_text(&mut self, text: &str) -> &mut Self {
+        self.text = text.to_string().into();
+        self.truncated = None;
+        self.update_truncated(0);
+        self
+    }
+
+    /// Set the text that is displayed.
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Set the text from styled spans.
+    pub fn set_styled_text(&mut self, text: &text::Text) -> &mut Self {
+        self.text = text.to_string().into();
+        self.truncated = None;
+        self.update_truncated(0);
+        self
+    }
+
+    /// Set the text from styled spans.
+    pub fn with_styled_text(mut self, text: &text::Text) -> Self {
+        self.set_styled_text(text);
+        self
+    }
+
+    /// The spinner of the line if it is of the given type.
+    #[must_use]
+    pub fn spinner_as<T: Frames>(&self) -> Option<&T> {
+        self.spinner.downcast_ref::<T>().ok()
+    }
+
+    /// The spinner of the line if it is of the given type.
+    #[must_use]
+    pub fn spinner_as_mut<T: Frames>(&mut self) -> Option<&mut T> {
+        self.spinner.downcast_mut::<T>().ok()
+    }
+
+    /// Attach a value to the line, e.g. the ID of the job
+    /// it displays, replacing any previous one.
+    ///
+    /// The tag is not displayed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Group, Line};
+    /// struct JobId(u32);
+    ///
+    /// let mut group = Group::new();
+    /// group
+    ///     .push(Line::new(spinner::dots()).with_tag(JobId(1)))
+    ///     .push(Line::new(spinner::dots()).with_tag(JobId(2)));
+    ///
+    /// let line = group
+    ///     .iter_as::<Line>()
+    ///     .find(|line| line.tag::<JobId>().is_some_and(|id| id.0 == 2));
+    /// assert!(line.is_some());
+    /// ```
+    pub fn with_tag<T: Any + Send + Sync>(mut self, tag: T) -> Self {
+        self.set_tag(tag);
+        self
+    }
+
+    /// Attach a value to the line, replacing any previous one.
+    pub fn set_tag<T: Any + Send + Sync>(&mut self, tag: T) -> &mut Self {
+        self.tag = Some(Box::new(tag));
+        self
+    }
+
+    /// Remove the attached value.
+    pub fn remove_tag(&mut self) -> &mut Self {
+        self.tag = None;
+        self
+    }
+
+    /// The attached value if it is of the given type.
+    #[must_use]
+    pub fn tag<T: Any>(&self) -> Option<&T> {
+        self.tag.as_deref()?.downcast_ref()
+    }
+
+    /// The attached value if it is of the given type.
+    #[must_use]
+    pub fn tag_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.tag.as_deref_mut()?.downcast_mut()
+    }
+
+    /// Replace the spinner of the line.
+    pub fn set_spinner(&mut self, spinner: impl Frames) -> &mut Self {
+        self.spinner = Box::new(spinner);
+        self
+    }
+
+    /// Set the state of the line.
+    ///
+    /// The line is timed from its creation until it is
+    /// finished, see [`Line::duration`]. Setting a finished
+    /// line to running again restarts the timer.
+    pub fn set_state(&mut self, state: FrameState) -> &mut Self {
+        match (self.finished, state.is_finished()) {
+            (None, true) => self.finished = Some(Instant::now()),
+            (Some(_), false) => {
+                self.started = Instant::now();
+                self.finished = None;
+            }
+            _ => {}
+        }
+        self.state = state;
+        self
+    }
+
+    /// Set the state of the line.
+    pub fn with_state(mut self, state: FrameState) -> Self {
+        self.set_state(state);
+        self
+    }
+
+    /// Style the spinner by the state of the line,
+    /// `None` leaves it as-is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, FrameState, Line, StateStyles};
+    /// let mut line = Line::new(spinner::from_array(["*"]))
+    ///     .with_text("build")
+    ///     .with_state_styles(Some(StateStyles::default()));
+    /// assert!(line.to_string().starts_with("\x1B[36m*\x1B[0m"));
+    ///
+    /// line.set_state(FrameState::Failed);
+    /// assert!(line.to_string().starts_with("\x1B[31m*\x1B[0m"));
+    /// ```
+    pub fn set_state_styles(&mut self, styles: Option<StateStyles>) -> &mut Self {
+        self.state_styles = styles;
+        self
+    }
+
+    /// Style the spinner by the state of the line,
+    /// `None` leaves it as-is.
+    pub fn with_state_styles(mut self, styles: Option<StateStyles>) -> Self {
+        self.state_styles = styles;
+        self
     }
 
-    /// Get text that is displayed.
+    /// The time the line started running.
     #[must_use]
-    pub fn text(&self) -> &str {
-        &self.text
+    pub fn started_at(&self) -> Instant {
+        self.started
     }
 
-    /// Set the text that is displayed.
-    pub fn set_text(&mut self, text: &str) -> &mut Self {
-        self.text = text.to_string().into();
+    /// The time the line finished, if it did.
+    #[must_use]
+    pub fn finished_at(&self) -> Option<Instant> {
+        self.finished
+    }
+
+    /// The time the line has been running for,
+    /// or the time it took to finish.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, time::DurationFormat, FrameState, Line};
+    /// let mut line = Line::new(spinner::dots())
+    ///     .with_text("build")
+    ///     .with_duration_format(Some(DurationFormat::Compact));
+    ///
+    /// line.set_state(FrameState::Done);
+    /// let took = line.duration();
+    ///
+    /// assert_eq!(line.duration(), took);
+    /// assert!(line.to_string().ends_with("build\x1B[0m 0s"));
+    /// ```
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.finished
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.started)
+    }
+
+    /// Display the duration after the text once the line
+    /// is finished in the given format, `None` hides it.
+    pub fn with_duration_format(mut self, format: Option<time::DurationFormat>) -> Self {
+        self.duration_format = format;
         self
     }
 
-    /// Set the text that is displayed.
-    pub fn with_text(mut self, text: &str) -> Self {
-        self.text = text.to_string().into();
+    /// Display the duration after the text once the line
+    /// is finished in the given format, `None` hides it.
+    pub fn set_duration_format(&mut self, format: Option<time::DurationFormat>) -> &mut Self {
+        self.duration_format = format;
+        self
+    }
+
+    /// Display the output of the given function instead of
+    /// the line once it is finished, e.g. to format finished
+    /// lines the same way across tools.
+    ///
+    /// The function is given the line to read its text,
+    /// [state](Frames::state) and [duration](Line::duration),
+    /// it must not display the line itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, time::DurationFormat, FrameState, Frames, Line};
+    /// let mut line = Line::new(spinner::dots())
+    ///     .with_text("build")
+    ///     .with_finish_fn(|line| {
+    ///         let mark = if line.state() == FrameState::Done { "✔" } else { "✘" };
+    ///         let took = DurationFormat::Compact.display(line.duration());
+    ///         format!("{mark} {} ({took})", line.text())
+    ///     });
+    ///
+    /// line.set_state(FrameState::Done);
+    /// assert_eq!(line.to_string(), "✔ build (0s)");
+    /// ```
+    pub fn with_finish_fn(mut self, f: impl Fn(&Line) -> String + Send + Sync + 'static) -> Self {
+        self.finish_fn = Some(Box::new(f));
+        self
+    }
+
+    /// Display the output of the given function instead of
+    /// the line once it is finished.
+    ///
+    /// See [`Line::with_finish_fn`].
+    pub fn set_finish_fn(
+        &mut self,
+        f: impl Fn(&Line) -> String + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.finish_fn = Some(Box::new(f));
         self
     }
 
@@ -257,58 +2094,639 @@ impl Line {
         self
     }
 
+    /// Toggle resetting all text styling after the spinner
+    /// and the text, enabled by default.
+    ///
+    /// This prevents unterminated styles
+    /// from affecting any output that follows.
+    pub fn set_style_reset(&mut self, reset: bool) -> &mut Self {
+        self.reset_style = reset;
+        self
+    }
+
+    /// Toggle resetting all text styling after the spinner
+    /// and the text, enabled by default.
+    ///
+    /// This prevents unterminated styles
+    /// from affecting any output that follows.
+    pub fn with_style_reset(mut self, reset: bool) -> Self {
+        self.reset_style = reset;
+        self
+    }
+
+    /// Toggle removing control characters from the text,
+    /// enabled by default.
+    ///
+    /// Only new lines and SGR escape sequences (colors and other
+    /// styling) are kept, so that untrusted text cannot move the
+    /// cursor or clear the screen.
+    pub fn set_sanitize(&mut self, sanitize: bool) -> &mut Self {
+        self.sanitize = sanitize;
+        self
+    }
+
+    /// Toggle removing control characters from the text,
+    /// enabled by default.
+    ///
+    /// Only new lines and SGR escape sequences (colors and other
+    /// styling) are kept, so that untrusted text cannot move the
+    /// cursor or clear the screen.
+    pub fn with_sanitize(mut self, sanitize: bool) -> Self {
+        self.sanitize = sanitize;
+        self
+    }
+
+    /// Wrap the text at the given width, continuation lines
+    /// are indented to start under the text.
+    ///
+    /// The width should not include the indentation of groups.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, term, Line};
+    /// Line::new(spinner::dots()).with_wrap_width(term::width());
+    /// ```
+    pub fn set_wrap_width(&mut self, width: Option<usize>) -> &mut Self {
+        self.wrap_width = width;
+        self
+    }
+
+    /// Wrap the text at the given width, continuation lines
+    /// are indented to start under the text.
+    ///
+    /// The width should not include the indentation of groups.
+    pub fn with_wrap_width(mut self, width: Option<usize>) -> Self {
+        self.wrap_width = width;
+        self
+    }
+
+    /// Shorten the text to fit the given width, the end
+    /// of the text is replaced with `…`.
+    ///
+    /// Text that looks like a file path is shortened with
+    /// [`truncate_path`](text::truncate_path) instead, keeping the
+    /// file name. Wrapped text and text with new lines is not
+    /// shortened. The width should not include the indentation
+    /// of groups.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Line};
+    /// let line = Line::new(spinner::empty())
+    ///     .with_spinner_visible(false)
+    ///     .with_style_reset(false)
+    ///     .with_max_width(Some(24))
+    ///     .with_text("~/code/work/project/src/main.rs");
+    ///
+    /// assert_eq!(line.to_string(), "~/…/project/src/main.rs");
+    /// ```
+    pub fn set_max_width(&mut self, width: Option<usize>) -> &mut Self {
+        self.max_width = width;
+        self.update_truncated(0);
+        self
+    }
+
+    /// Shorten the text to fit the given width, the end
+    /// of the text is replaced with `…`.
+    ///
+    /// See [`Line::set_max_width`].
+    pub fn with_max_width(mut self, width: Option<usize>) -> Self {
+        self.set_max_width(width);
+        self
+    }
+
+    /// Pad the spinner to at least the given width,
+    /// so that texts of lines with spinners of different
+    /// widths start in the same column.
+    ///
+    /// See also [`Group::set_align_spinners`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Line};
+    /// let line = Line::new(spinner::dots())
+    ///     .with_style_reset(false)
+    ///     .with_spinner_width(Some(2))
+    ///     .with_text("fetching");
+    ///
+    /// assert_eq!(line.to_string(), "⠋  fetching");
+    /// ```
+    pub fn set_spinner_width(&mut self, width: Option<usize>) -> &mut Self {
+        self.spinner_width = width;
+        self
+    }
+
+    /// Pad the spinner to at least the given width.
+    ///
+    /// See [`Line::set_spinner_width`].
+    pub fn with_spinner_width(mut self, width: Option<usize>) -> Self {
+        self.spinner_width = width;
+        self
+    }
+
+    /// Make the text a hyperlink to the given URL
+    /// in terminals that support it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Line};
+    /// let mut line = Line::new(spinner::dots()).with_text("build log");
+    /// line.set_link(Some("https://example.com/builds/42"));
+    /// ```
+    pub fn set_link(&mut self, url: Option<&str>) -> &mut Self {
+        self.link = url.map(|url| url.to_string().into());
+        self
+    }
+
+    /// Make the text a hyperlink to the given URL
+    /// in terminals that support it.
+    pub fn with_link(mut self, url: Option<&str>) -> Self {
+        self.set_link(url);
+        self
+    }
+
+    /// Get the badge that is displayed after the text.
+    #[must_use]
+    pub fn badge(&self) -> Option<&str> {
+        self.badge.as_deref()
+    }
+
+    /// Set a short annotation displayed after the text
+    /// in the badge style, e.g. `[retrying]`.
+    ///
+    /// The badge is kept when the text changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Line};
+    /// let mut line = Line::new(spinner::dots()).with_text("fetching index");
+    /// line.set_badge(Some("[retrying]"));
+    ///
+    /// line.set_text("fetching index again");
+    /// assert_eq!(line.badge(), Some("[retrying]"));
+    /// ```
+    pub fn set_badge(&mut self, badge: Option<&str>) -> &mut Self {
+        self.badge = badge.map(|badge| badge.to_string().into());
+        self.update_truncated(0);
+        self
+    }
+
+    /// Set a short annotation displayed after the text
+    /// in the badge style, e.g. `[retrying]`.
+    ///
+    /// The badge is kept when the text changes.
+    pub fn with_badge(mut self, badge: Option<&str>) -> Self {
+        self.set_badge(badge);
+        self
+    }
+
+    /// Set the style of the badge, yellow by default.
+    pub fn set_badge_style(&mut self, style: text::Style) -> &mut Self {
+        self.badge_style = style;
+        self
+    }
+
+    /// Set the style of the badge, yellow by default.
+    pub fn with_badge_style(mut self, style: text::Style) -> Self {
+        self.badge_style = style;
+        self
+    }
+
+    /// The width of the badge including
+    /// the space before it.
+    fn badge_width(&self) -> usize {
+        self.badge.as_deref().map_or(0, |badge| {
+            let space = self.spinner_printed() || !self.text.is_empty();
+            usize::from(space) + display_width(badge)
+        })
+    }
+
+    /// The duration including the space before it
+    /// if the line finished and it is displayed.
+    fn finished_duration(&self) -> Option<String> {
+        let format = self.duration_format?;
+        self.finished?;
+
+        let space = if self.spinner_printed() || !self.text.is_empty() {
+            " "
+        } else {
+            ""
+        };
+        Some(format!("{space}{}", format.display(self.duration())))
+    }
+
+    /// The output of the finish function
+    /// if the line finished and there is one.
+    fn finish_output(&self) -> Option<String> {
+        self.finished?;
+        self.finish_fn.as_ref().map(|finish| finish(self))
+    }
+
+    /// Write the badge including the space before it.
+    fn fmt_badge(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(badge) = &self.badge else {
+            return Ok(());
+        };
+
+        if self.spinner_printed() || !self.text.is_empty() {
+            f.write_char(' ')?;
+        }
+        self.badge_style.fmt(f)?;
+        Sanitized(badge).fmt(f)?;
+        if !self.badge_style.is_plain() {
+            ResetStyle.fmt(f)?;
+        }
+
+        Ok(())
+    }
+
     /// Print ANSI codes that clears the frames displayed
-    /// by this line.
+    /// by this line, including all rows of wrapped text.
+    ///
+    /// This expects the cursor to be right after the line
+    /// as it was printed on its own, inside a [`Group`]
+    /// the group clears its lines.
     ///
     /// # Example
     ///
     /// ```
     /// # use termspin::Line;
-    /// # let line = Line::new(termspin::spinner::from_iter([""]));
-    /// print!("{}", line.clear());
+    /// let line = Line::new(termspin::spinner::empty()).with_text("a\nb\nc");
+    /// print!("{line}");
+    ///
+    /// assert_eq!(line.clear().to_string(), "\x1B[2A\r\x1B[0J");
     /// ```
     #[must_use]
     pub fn clear(&self) -> impl core::fmt::Display + '_ {
-        DisplayFn::new(|f| <Self as Frames>::clear(self, f))
+        DisplayFn::new(|f| {
+            CursorUp(self.displayed_lines().saturating_sub(1)).fmt(f)?;
+            <Self as Frames>::clear(self, f)
+        })
     }
 
     /// Turn this line into [`SharedFrames`].
     pub fn shared(self) -> SharedFrames<Self> {
         SharedFrames::new(self)
     }
+
+    /// The display width of the last row of the line
+    /// with the given finished duration and spinner alignment.
+    fn last_row_width(&self, duration: Option<&str>, align: usize) -> usize {
+        let text = self.fitted_text(align);
+        let last_line = text.rsplit('\n').next().unwrap_or_default();
+        self.prefix_width(align)
+            + display_width(last_line)
+            + duration.map_or(0, display_width)
+            + self.badge_width()
+    }
+
+    fn spinner_printed(&self) -> bool {
+        self.show_spinner && self.spinner.print_len() != Some(0)
+    }
+
+    /// A hash of everything that affects the output
+    /// except for the spinner.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.show_spinner.hash(&mut hasher);
+        self.spinner_width.hash(&mut hasher);
+        self.reset_style.hash(&mut hasher);
+        self.sanitize.hash(&mut hasher);
+        self.wrap_width.hash(&mut hasher);
+        self.max_width.hash(&mut hasher);
+        self.link.hash(&mut hasher);
+        self.state.hash(&mut hasher);
+        self.state_styles.hash(&mut hasher);
+        self.text.hash(&mut hasher);
+        self.badge.hash(&mut hasher);
+        self.badge_style.hash(&mut hasher);
+        self.finished.hash(&mut hasher);
+        self.duration_format.hash(&mut hasher);
+        self.finish_fn.is_some().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The width of the current spinner frame without padding.
+    fn measure_spinner(&self) -> usize {
+        self.spinner
+            .print_len()
+            .unwrap_or_else(|| WidthCounter::measure(&self.spinner))
+    }
+
+    /// The width of everything printed before the text
+    /// with the spinner padded to the given width.
+    fn prefix_width(&self, align: usize) -> usize {
+        if !self.spinner_printed() {
+            return 0;
+        }
+
+        let spinner_width = self
+            .measure_spinner()
+            .max(self.spinner_width.unwrap_or_default())
+            .max(align);
+
+        spinner_width + usize::from(!self.text.is_empty())
+    }
+
+    /// The width available for the text with the maximum width,
+    /// `None` if the text is not shortened.
+    fn available_width(&self, align: usize) -> Option<usize> {
+        if self.wrap_width.is_some() || self.text.contains('\n') {
+            return None;
+        }
+
+        let width = self.max_width?;
+        Some(width.saturating_sub(self.prefix_width(align) + self.badge_width()))
+    }
+
+    /// The text shortened to the maximum width, the cached
+    /// text is used unless the available width changed.
+    fn fitted_text(&self, align: usize) -> Cow<'_, str> {
+        let Some(available) = self.available_width(align) else {
+            return Cow::Borrowed(&self.text);
+        };
+
+        match &self.truncated {
+            _ if display_width(&self.text) <= available => Cow::Borrowed(&self.text),
+            Some((width, text)) if *width == available => Cow::Borrowed(text),
+            _ => Cow::Owned(self.truncate(available)),
+        }
+    }
+
+    /// Shorten the text to the given width.
+    fn truncate(&self, width: usize) -> String {
+        if text::is_path_like(&self.text) {
+            text::truncate_path(&self.text, width).into_owned()
+        } else {
+            text::truncate_end(&self.text, width)
+        }
+    }
+
+    /// Cache the text shortened to the width available
+    /// with the given spinner alignment.
+    fn update_truncated(&mut self, align: usize) {
+        let Some(available) = self.available_width(align) else {
+            self.truncated = None;
+            return;
+        };
+
+        if display_width(&self.text) <= available {
+            self.truncated = None;
+        } else if !matches!(&self.truncated, Some((width, _)) if *width == available) {
+            self.truncated = Some((available, self.truncate(available)));
+        }
+    }
+
+    /// Write the text and return the amount of rows it occupies.
+    fn fmt_text(
+        &self,
+        align: usize,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> Result<usize, core::fmt::Error> {
+        let prefix_width = self.prefix_width(align);
+        if self.wrap_width.is_some() || self.text.contains('\n') {
+            return self.fmt_lines(self.wrap_width, prefix_width, Some(f));
+        }
+
+        let text = self.fitted_text(align);
+        if self.sanitize {
+            Sanitized(&text).fmt(f)?;
+        } else {
+            text.fmt(f)?;
+        }
+
+        Ok(1)
+    }
+
+    /// Write the text split at new lines and wrapped to the
+    /// given width if any, and return the amount of printed lines.
+    ///
+    /// Nothing is written if no formatter is given.
+    fn fmt_lines(
+        &self,
+        width: Option<usize>,
+        prefix_width: usize,
+        mut f: Option<&mut std::fmt::Formatter<'_>>,
+    ) -> Result<usize, core::fmt::Error> {
+        let available = width.map_or(usize::MAX, |width| {
+            width.saturating_sub(prefix_width).max(1)
+        });
+        let mut lines = 1;
+        let mut column = 0;
+
+        for token in Tokens(&self.text) {
+            match token {
+                Token::Control('\n') => {
+                    lines += 1;
+                    column = 0;
+
+                    if let Some(f) = f.as_deref_mut() {
+                        // Clear leftovers of overwritten output.
+                        ClearLineEnd.fmt(f)?;
+                        writeln!(f)?;
+                        write!(f, "{:prefix_width$}", "")?;
+                    }
+                }
+                Token::Text(text) => {
+                    for c in text.chars() {
+                        let c_width = char_width(c);
+
+                        if column > 0 && column + c_width > available {
+                            lines += 1;
+                            column = 0;
+
+                            if let Some(f) = f.as_deref_mut() {
+                                writeln!(f)?;
+                                write!(f, "{:prefix_width$}", "")?;
+                            }
+                        }
+
+                        column += c_width;
+
+                        if let Some(f) = f.as_deref_mut() {
+                            f.write_char(c)?;
+                        }
+                    }
+                }
+                Token::Escape(seq) if !self.sanitize || token.is_sgr() => {
+                    if let Some(f) = f.as_deref_mut() {
+                        f.write_str(seq)?;
+                    }
+                }
+                Token::Control(c) if !self.sanitize => {
+                    if let Some(f) = f.as_deref_mut() {
+                        f.write_char(c)?;
+                    }
+                }
+                Token::Escape(_) | Token::Control(_) => {}
+            }
+        }
+
+        Ok(lines)
+    }
 }
 
 impl Frames for Line {
     fn advance(&mut self) {
         self.spinner.advance();
     }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.spinner.advance_at(now);
+    }
+
     fn reset(&mut self) {
         self.spinner.reset();
     }
 
     fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
         "\r".fmt(f)?;
-        ClearLine.fmt(f)
+        if self.displayed_lines() > 1 {
+            ClearBelow.fmt(f)
+        } else {
+            ClearLine.fmt(f)
+        }
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        "\r".fmt(f)
+    }
+
+    fn state(&self) -> FrameState {
+        self.state
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.spinner.progress()
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        self.spinner.interval()
+    }
+
+    fn label(&self) -> Option<String> {
+        let text = single_line(&self.text);
+        (!text.is_empty()).then_some(text)
     }
 
     fn lines(&self) -> usize {
-        1
+        self.lines_aligned(0)
+    }
+
+    fn displayed_lines(&self) -> usize {
+        self.printed_lines.unwrap_or_else(|| self.lines())
+    }
+
+    fn is_dirty(&self) -> bool {
+        let spinner_printed =
+            self.spinner_printed() && !(self.finished.is_some() && self.finish_fn.is_some());
+
+        self.clean != Some(self.fingerprint()) || (spinner_printed && self.spinner.is_dirty())
+    }
+
+    fn mark_clean(&mut self) {
+        self.mark_clean_aligned(0);
     }
 }
 
 impl core::fmt::Display for Line {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_aligned(0, f)
+    }
+}
+
+impl Line {
+    /// [`Frames::lines`] with the spinner padded to the given width.
+    fn lines_aligned(&self, align: usize) -> usize {
+        if let Some(output) = self.finish_output() {
+            output.split('\n').count()
+        } else if self.wrap_width.is_some() || self.text.contains('\n') {
+            self.fmt_lines(self.wrap_width, self.prefix_width(align), None)
+                .unwrap_or(1)
+        } else {
+            1
+        }
+    }
+
+    /// [`Frames::mark_clean`] with the spinner padded to the given width.
+    fn mark_clean_aligned(&mut self, align: usize) {
+        self.update_truncated(align);
+        self.printed_width = match self.finish_output() {
+            Some(output) => display_width(output.rsplit('\n').next().unwrap_or_default()),
+            None => self.last_row_width(self.finished_duration().as_deref(), align),
+        };
+        self.printed_lines = Some(self.lines_aligned(align));
+        self.clean = Some(self.fingerprint());
+        self.spinner.mark_clean();
+    }
+
+    /// Write the line with the spinner padded to the given width.
+    fn fmt_aligned(&self, align: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(output) = self.finish_output() {
+            f.write_str(&output)?;
+
+            let last_row = output.rsplit('\n').next().unwrap_or_default();
+            if display_width(last_row) < self.printed_width {
+                ClearLineEnd.fmt(f)?;
+            }
+            return Ok(());
+        }
+
         if self.show_spinner {
+            let style = self
+                .state_styles
+                .map_or_else(text::Style::new, |styles| styles.get(self.state));
+            style.fmt(f)?;
             self.spinner.fmt(f)?;
+            if !style.is_plain() {
+                ResetStyle.fmt(f)?;
+            }
+        }
+
+        let width = self.spinner_width.unwrap_or_default().max(align);
+        if self.spinner_printed() && width > 0 {
+            let padding = width.saturating_sub(self.measure_spinner());
+            write!(f, "{:padding$}", "")?;
+        }
 
-            let spinner_printed = self.spinner.print_len().map_or(true, |l| l != 0);
+        if self.spinner_printed() {
+            if self.reset_style {
+                ResetStyle.fmt(f)?;
+            }
 
-            if spinner_printed && !self.text.is_empty() {
+            if !self.text.is_empty() {
                 f.write_char(' ')?;
             }
         }
 
-        self.text.fmt(f)?;
+        match &self.link {
+            Some(url) if !self.text.is_empty() => {
+                Hyperlink(url, DisplayFn::new(|f| self.fmt_text(align, f).map(drop))).fmt(f)?;
+            }
+            _ => {
+                self.fmt_text(align, f)?;
+            }
+        }
+
+        if self.reset_style {
+            ResetStyle.fmt(f)?;
+        }
+        let duration = self.finished_duration();
+        if let Some(duration) = &duration {
+            f.write_str(duration)?;
+        }
+        self.fmt_badge(f)?;
+
+        // Clear any remaining characters of a previous longer output
+        // if the line was overwritten without being cleared.
+        if self.last_row_width(duration.as_deref(), align) < self.printed_width {
+            ClearLineEnd.fmt(f)?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,178 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::{
+    ansi::{ClearBelow, ClearLine, ClearLineEnd, ResetStyle},
+    text::Style,
+    util::Sanitized,
+    Frames, SharedFrames,
+};
+
+/// Frames that display the last entries of a log,
+/// each entry on its own line.
+///
+/// Older entries are dropped once more than the
+/// given amount of entries are pushed.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Frames, Group, Line, LogTail};
+/// let log = LogTail::new(2).shared();
+///
+/// let mut group = Group::new();
+/// group.push(Line::new(spinner::dots()).with_text("building"));
+/// group.push(log.clone());
+///
+/// log.lock().push("compiling a").push("compiling b").push("compiling c");
+///
+/// assert_eq!(log.lines(), 2);
+/// assert_eq!(log.lock().entries().collect::<Vec<_>>(), ["compiling b", "compiling c"]);
+/// ```
+#[must_use]
+#[derive(Debug)]
+pub struct LogTail {
+    entries: VecDeque<String>,
+    capacity: usize,
+    style: Style,
+    /// The amount of entries ever pushed.
+    pushed: u64,
+    /// The amount of rows when the log was marked clean.
+    printed_lines: usize,
+    /// The pushed entries and the style when marked clean.
+    clean: Option<(u64, Style)>,
+}
+
+impl LogTail {
+    /// Create a log that displays at most
+    /// the given amount of entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            style: Style::new().with_dim(true),
+            pushed: 0,
+            printed_lines: 0,
+            clean: None,
+        }
+    }
+
+    /// Add an entry after the existing ones,
+    /// each line of the text is a separate entry.
+    pub fn push(&mut self, text: &str) -> &mut Self {
+        for line in text.lines() {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            if self.capacity > 0 {
+                self.entries.push_back(line.to_string());
+            }
+            self.pushed += 1;
+        }
+        self
+    }
+
+    /// Remove all entries.
+    pub fn clear_entries(&mut self) -> &mut Self {
+        self.entries.clear();
+        self.pushed += 1;
+        self
+    }
+
+    /// The displayed entries, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// The amount of displayed entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The maximum amount of displayed entries.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Set the style of the entries, dimmed by default.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the entries, dimmed by default.
+    pub fn set_style(&mut self, style: Style) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Turn this log into [`SharedFrames`].
+    pub fn shared(self) -> SharedFrames<Self> {
+        SharedFrames::new(self)
+    }
+}
+
+impl Display for LogTail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                '\n'.fmt(f)?;
+            }
+
+            self.style.fmt(f)?;
+            Sanitized(entry).fmt(f)?;
+            if !self.style.is_plain() {
+                ResetStyle.fmt(f)?;
+            }
+            ClearLineEnd.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Frames for LogTail {
+    fn advance(&mut self) {}
+
+    fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.displayed_lines() {
+            0 => Ok(()),
+            1 => {
+                "\r".fmt(f)?;
+                ClearLine.fmt(f)
+            }
+            _ => {
+                "\r".fmt(f)?;
+                ClearBelow.fmt(f)
+            }
+        }
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        "\r".fmt(f)
+    }
+
+    fn lines(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn displayed_lines(&self) -> usize {
+        self.printed_lines
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some((self.pushed, self.style))
+    }
+
+    fn mark_clean(&mut self) {
+        self.printed_lines = self.entries.len();
+        self.clean = Some((self.pushed, self.style));
+    }
+}
@@ -1,15 +1,613 @@
 use std::{
-    io,
-    sync::{Arc, Mutex},
+    collections::BTreeMap,
+    fmt::{Display, Write as _},
+    fs, io,
+    ops::Range,
+    path::PathBuf,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, Weak},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use crate::{
-    util::{DisplayFn, SHARED_LOCK},
-    Frames,
+    ansi::{
+        ClearBelow, ClearLineEnd, CursorUp, CursorVisible, EraseDisplay, SetTitle, TaskbarProgress,
+    },
+    builder::LoopBuilder,
+    report::{line_duration, state_name},
+    term,
+    term::TermMode,
+    util::{display_width, single_line, DisplayFn, SHARED_LOCK},
+    FrameState, Frames,
 };
 
+/// The way a [`Loop`] replaces previously printed frames.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderMode {
+    /// Clear the printed frames before printing
+    /// the next ones.
+    #[default]
+    Clear,
+    /// Print the next frames over the printed ones,
+    /// only clearing leftover characters.
+    ///
+    /// This reduces flickering on slow terminals.
+    Overwrite,
+}
+
+/// Determines when a [`Loop`] running on a stream
+/// flushes the stream.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlushPolicy {
+    /// Flush after every frame.
+    #[default]
+    EveryFrame,
+    /// Flush after every n-th frame.
+    EveryNFrames(usize),
+    /// Never flush, leaving it up to the stream.
+    Never,
+    /// Flush only if the output differs from
+    /// the previous frame.
+    OnChange,
+}
+
+/// Determines how a stopped [`Loop`] continues
+/// when it is started again.
+///
+/// Frames that are still displayed from the previous
+/// run are cleared either way.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RestartPolicy {
+    /// Continue with the current state of the frames.
+    #[default]
+    Resume,
+    /// Reset the frames before continuing.
+    Reset,
+}
+
+/// Determines what a [`Loop`] does while nobody is watching,
+/// i.e. the terminal is unfocused (see [`Loop::set_focused`])
+/// or the process is in the background (see [`term::is_foreground`]).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackgroundPolicy {
+    /// Keep updating at the regular interval.
+    #[default]
+    Normal,
+    /// Update at most once per the given interval.
+    Throttle(Duration),
+    /// Clear the frames and stop updating them,
+    /// like [`Loop::pause`].
+    Pause,
+}
+
+/// Where a [`Loop`] reports the progress of its frames
+/// in addition to printing them, see [`Frames::progress`].
+///
+/// The report is only printed when it changes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusReport {
+    /// Do not report progress.
+    #[default]
+    Off,
+    /// Set the window title to the percentage,
+    /// the previous title is not restored.
+    WindowTitle,
+    /// Report progress to the taskbar via OSC 9;4,
+    /// the indicator is removed when the loop stops.
+    Taskbar,
+    /// Set the iTerm2 session badge to the percentage,
+    /// the badge is removed when the loop stops.
+    #[cfg(feature = "iterm2")]
+    Badge,
+}
+
+impl StatusReport {
+    /// The escape sequence that reports the progress
+    /// of the given frames.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn report(self, frames: &dyn Frames) -> Option<String> {
+        let percent = frames
+            .progress()
+            .map(|p| (p.clamp(0.0, 1.0) * 100.0).floor() as u8);
+
+        match self {
+            StatusReport::Off => None,
+            StatusReport::WindowTitle => {
+                Some(SetTitle(percent.map_or_else(String::new, |p| format!("{p}%"))).to_string())
+            }
+            StatusReport::Taskbar => {
+                let progress = match (frames.state(), percent) {
+                    (FrameState::Failed, p) => TaskbarProgress::Error(p.unwrap_or(100)),
+                    (FrameState::Paused, p) => TaskbarProgress::Paused(p.unwrap_or(0)),
+                    (_, Some(p)) => TaskbarProgress::Normal(p),
+                    (_, None) => TaskbarProgress::Indeterminate,
+                };
+                Some(progress.to_string())
+            }
+            #[cfg(feature = "iterm2")]
+            StatusReport::Badge => Some(
+                crate::ansi::SetBadge(percent.map_or_else(String::new, |p| format!("{p}%")))
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// The escape sequence that removes the report
+    /// when the loop stops, if any.
+    fn clear(self) -> Option<String> {
+        match self {
+            StatusReport::Off | StatusReport::WindowTitle => None,
+            StatusReport::Taskbar => Some(TaskbarProgress::Hidden.to_string()),
+            #[cfg(feature = "iterm2")]
+            StatusReport::Badge => Some(crate::ansi::SetBadge("").to_string()),
+        }
+    }
+}
+
+/// Writes a one-line summary of the frames of a [`Loop`]
+/// somewhere else whenever it changes, e.g. for a tmux status bar
+/// or a shell prompt, see [`Loop::status_export`].
+///
+/// The summary defaults to the printed text on a single line
+/// without escape sequences. Formatting the frames for the summary
+/// does not change what the loop clears, as frames record what they
+/// displayed in [`Frames::mark_clean`]. The summary is written
+/// along with the frames after they are unlocked, errors writing
+/// it are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{progress::Progress, Frames, Line, Loop, StatusExport};
+/// # use std::time::Duration;
+/// let l = Loop::new(Duration::from_millis(100), Line::new(Progress::new()));
+///
+/// let path = std::env::temp_dir().join("termspin-status");
+/// l.status_export(StatusExport::file(path).with_summary(|frames| {
+///     frames
+///         .progress()
+///         .map_or_else(String::new, |p| format!("{:.0}%", p * 100.0))
+/// }));
+/// ```
+pub struct StatusExport {
+    /// Shared with the snapshot that writes the summary.
+    target: Arc<Mutex<ExportTarget>>,
+    summary: Summary,
+    last: Option<String>,
+}
+
+type Summary = Box<dyn Fn(&dyn Frames) -> String + Send>;
+
+enum ExportTarget {
+    File(PathBuf),
+    Writer(Box<dyn io::Write + Send>),
+}
+
+impl StatusExport {
+    /// Replace the content of the file at the given path
+    /// with the summary.
+    ///
+    /// The file is replaced by renaming a temporary file
+    /// next to it, so readers never see partial content.
+    #[must_use]
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::new(ExportTarget::File(path.into()))
+    }
+
+    /// Write the summary to the given writer followed
+    /// by a new line, e.g. to a socket.
+    #[must_use]
+    pub fn writer(writer: impl io::Write + Send + 'static) -> Self {
+        Self::new(ExportTarget::Writer(Box::new(writer)))
+    }
+
+    fn new(target: ExportTarget) -> Self {
+        Self {
+            target: Arc::new(Mutex::new(target)),
+            summary: Box::new(|frames| single_line(&frames.to_string())),
+            last: None,
+        }
+    }
+
+    /// Set the function that summarizes the frames.
+    #[must_use]
+    pub fn with_summary(
+        mut self,
+        summary: impl Fn(&dyn Frames) -> String + Send + 'static,
+    ) -> Self {
+        self.summary = Box::new(summary);
+        self
+    }
+
+    /// Record the summary of the given frames, returns
+    /// the summary to write if it changed.
+    fn update(&mut self, frames: &dyn Frames) -> Option<String> {
+        let summary = (self.summary)(frames);
+        if self.last.as_ref() == Some(&summary) {
+            return None;
+        }
+
+        self.last = Some(summary.clone());
+        Some(summary)
+    }
+}
+
+impl core::fmt::Debug for StatusExport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatusExport")
+            .field("target", &self.target)
+            .field("last", &self.last)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ExportTarget {
+    fn write(&mut self, summary: &str) {
+        let _ = match self {
+            ExportTarget::File(path) => {
+                let mut tmp = path.clone().into_os_string();
+                tmp.push(".tmp");
+                fs::write(&tmp, summary).and_then(|()| fs::rename(&tmp, &*path))
+            }
+            ExportTarget::Writer(writer) => {
+                writeln!(writer, "{summary}").and_then(|()| writer.flush())
+            }
+        };
+    }
+}
+
+impl core::fmt::Debug for ExportTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportTarget::File(path) => f.debug_tuple("File").field(path).finish(),
+            ExportTarget::Writer(_) => f.write_str("Writer"),
+        }
+    }
+}
+
+/// The stream of [`Loop::announce_to`], shared
+/// with the snapshots that write to it.
+struct AnnounceStream(Mutex<Box<dyn io::Write + Send>>);
+
+impl AnnounceStream {
+    fn write(&self, sentences: &str) {
+        let mut stream = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = stream
+            .write_all(sentences.as_bytes())
+            .and_then(|()| stream.flush());
+    }
+}
+
+impl core::fmt::Debug for AnnounceStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnnounceStream").finish_non_exhaustive()
+    }
+}
+
+/// Announces state changes of labeled frames as sentences,
+/// see [`Loop::announce`].
+struct Announcer {
+    /// Announcements are printed with the frames if not set.
+    stream: Option<Arc<AnnounceStream>>,
+    /// The last seen states by the address of the frames.
+    states: BTreeMap<usize, FrameState>,
+    /// The machine-readable mode the changes are
+    /// announced in instead of sentences, if any.
+    events: Option<TermMode>,
+}
+
+impl Announcer {
+    fn new(stream: Option<Box<dyn io::Write + Send>>) -> Self {
+        Self {
+            stream: stream.map(|stream| Arc::new(AnnounceStream(Mutex::new(stream)))),
+            states: BTreeMap::new(),
+            events: None,
+        }
+    }
+
+    fn events(mode: TermMode) -> Self {
+        Self {
+            events: Some(mode),
+            ..Self::new(None)
+        }
+    }
+
+    /// The sentences announcing the changes since the last call,
+    /// each on its own line.
+    fn announcements(&mut self, frames: &dyn Frames) -> String {
+        let mut states = BTreeMap::new();
+        let mut sentences = String::new();
+
+        visit_labeled(frames, &mut |frames, label| {
+            // Children of groups are boxed or shared,
+            // so their address identifies them.
+            let id = std::ptr::from_ref(frames).cast::<()>() as usize;
+            let state = frames.state();
+            let label = label.trim_end_matches(['.', ':', ' ']);
+
+            let prev = self.states.get(&id).copied();
+            match self.events {
+                Some(TermMode::JsonLines) if prev != Some(state) => {
+                    write_json_event(&mut sentences, label, state, line_duration(frames));
+                }
+                Some(TermMode::GithubActions) => {
+                    write_github_event(&mut sentences, label, prev, state);
+                }
+                _ => {}
+            }
+            if self.events.is_some() {
+                states.insert(id, state);
+                return;
+            }
+
+            let event = match (prev, state) {
+                (Some(FrameState::Running), FrameState::Paused) => Some("paused"),
+                (Some(FrameState::Paused), FrameState::Running) => Some("resumed"),
+                (None | Some(FrameState::Done | FrameState::Failed), FrameState::Running) => {
+                    Some("started")
+                }
+                (prev, FrameState::Done) if prev != Some(FrameState::Done) => Some("finished"),
+                (prev, FrameState::Failed) if prev != Some(FrameState::Failed) => Some("failed"),
+                _ => None,
+            };
+
+            if let Some(event) = event {
+                sentences.push_str(label);
+                sentences.push(' ');
+                sentences.push_str(event);
+                sentences.push_str(".\n");
+            }
+
+            states.insert(id, state);
+        });
+
+        self.states = states;
+        sentences
+    }
+}
+
+/// Append a state change as a JSON line.
+fn write_json_event(buf: &mut String, task: &str, state: FrameState, elapsed: Option<Duration>) {
+    buf.push_str("{\"task\":\"");
+    for c in task.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(buf, "\\u{:04x}", u32::from(c));
+            }
+            c => buf.push(c),
+        }
+    }
+    let _ = write!(buf, "\",\"state\":\"{}\"", state_name(state));
+    if let Some(elapsed) = elapsed {
+        let _ = write!(buf, ",\"elapsed_ms\":{}", elapsed.as_millis());
+    }
+    buf.push_str("}\n");
+}
+
+/// Append the workflow commands for a state change
+/// in a GitHub Actions log.
+fn write_github_event(buf: &mut String, task: &str, prev: Option<FrameState>, state: FrameState) {
+    let started = matches!(prev, None | Some(FrameState::Done | FrameState::Failed))
+        && state == FrameState::Running;
+    let finished = state.is_finished() && prev != Some(state);
+
+    if started {
+        buf.push_str("::group::");
+        write_github_data(buf, task);
+        buf.push('\n');
+    }
+    if finished && prev.is_some_and(|prev| !prev.is_finished()) {
+        buf.push_str("::endgroup::\n");
+    }
+    if finished && state == FrameState::Failed {
+        buf.push_str("::error::");
+        write_github_data(buf, task);
+        buf.push_str(" failed\n");
+    }
+}
+
+/// Append the text escaped as the data of a workflow command.
+fn write_github_data(buf: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '%' => buf.push_str("%25"),
+            '\r' => buf.push_str("%0D"),
+            '\n' => buf.push_str("%0A"),
+            c => buf.push(c),
+        }
+    }
+}
+
+/// Call the given function with the frames and all
+/// nested children that have a label.
+fn visit_labeled(frames: &dyn Frames, f: &mut dyn FnMut(&dyn Frames, &str)) {
+    if let Some(label) = frames.label() {
+        f(frames, &label);
+    }
+
+    frames.for_each_child(&mut |child| visit_labeled(child, f));
+}
+
+/// Timing data of a tick that took longer than
+/// allowed, see [`Loop::watchdog`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StalledTick {
+    /// The number of the tick, starting at 1.
+    pub tick: u64,
+    /// The interval of the loop.
+    pub interval: Duration,
+    /// The time spent clearing the previous frames.
+    pub clear: Duration,
+    /// The time spent rendering the frames.
+    pub render: Duration,
+    /// The time spent advancing the frames.
+    pub advance: Duration,
+    /// The time spent writing the output to the stream.
+    pub write: Duration,
+}
+
+impl StalledTick {
+    /// The total time spent on the tick.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.clear + self.render + self.advance + self.write
+    }
+}
+
+impl core::fmt::Display for StalledTick {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tick {} took {:?} with an interval of {:?} \
+            (clear: {:?}, render: {:?}, advance: {:?}, write: {:?})",
+            self.tick,
+            self.total(),
+            self.interval,
+            self.clear,
+            self.render,
+            self.advance,
+            self.write
+        )
+    }
+}
+
+struct Watchdog {
+    threshold: Duration,
+    callback: Box<dyn FnMut(&StalledTick) + Send>,
+}
+
+/// Write the output as supported by the terminal mode.
+fn write_mode(
+    mode: TermMode,
+    printed_width: &mut usize,
+    f: &mut impl FnMut(&dyn core::fmt::Display, Output) -> io::Result<()>,
+    out: &dyn core::fmt::Display,
+    output: Output,
+) -> io::Result<()> {
+    match (mode, output) {
+        (TermMode::Ansi, _) | (TermMode::JsonLines | TermMode::GithubActions, Output::Events) => {
+            f(out, output)
+        }
+        (TermMode::Plain | TermMode::JsonLines | TermMode::GithubActions, _)
+        | (TermMode::CarriageReturn, Output::Clear { .. } | Output::Events) => Ok(()),
+        (TermMode::CarriageReturn, Output::Frames { .. }) => {
+            let line = single_line(&out.to_string());
+            let width = display_width(&line);
+            let padding = printed_width.saturating_sub(width);
+            *printed_width = width;
+
+            f(&format_args!("\r{line}{:padding$}", ""), output)
+        }
+    }
+}
+
+/// The output of a tick, rendered while the frames are
+/// locked and written after the lock is released.
+///
+/// The buffer is reused between ticks.
+#[derive(Debug, Default)]
+struct Snapshot {
+    buf: String,
+    writes: Vec<(Range<usize>, Output)>,
+    /// A changed status summary and where to export it.
+    export: Option<(Arc<Mutex<ExportTarget>>, String)>,
+    /// Announcements for the stream of [`Loop::announce_to`].
+    announcements: Option<(Arc<AnnounceStream>, String)>,
+}
+
+impl Snapshot {
+    fn clear(&mut self) {
+        self.buf.clear();
+        self.writes.clear();
+        self.export = None;
+        self.announcements = None;
+    }
+
+    fn push(&mut self, out: &dyn core::fmt::Display, output: Output) {
+        self.push_with(output, |buf| {
+            let _ = write!(buf, "{out}");
+        });
+    }
+
+    /// Record the output appended to the buffer by the function.
+    fn push_with(&mut self, output: Output, f: impl FnOnce(&mut String)) {
+        let start = self.buf.len();
+        f(&mut self.buf);
+        self.writes.push((start..self.buf.len(), output));
+    }
+
+    fn write(
+        &self,
+        f: &mut impl FnMut(&dyn core::fmt::Display, Output) -> io::Result<()>,
+    ) -> io::Result<()> {
+        for (range, output) in &self.writes {
+            f(&&self.buf[range.clone()], *output)?;
+        }
+
+        if let Some((target, summary)) = &self.export {
+            target
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .write(summary);
+        }
+        if let Some((stream, sentences)) = &self.announcements {
+            stream.write(sentences);
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the output to the stream and flush it if needed.
+fn write_stream(
+    stream: &mut impl io::Write,
+    out: &dyn core::fmt::Display,
+    output: Output,
+) -> io::Result<()> {
+    write!(stream, "{out}")?;
+
+    match output {
+        Output::Clear { flush: true } | Output::Frames { flush: true } | Output::Events => {
+            stream.flush()
+        }
+        Output::Clear { flush: false } | Output::Frames { flush: false } => Ok(()),
+    }
+}
+
+/// A pending redraw requested by [`Loop::invalidate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Invalidation {
+    Redraw,
+    Erase,
+}
+
+/// How to replace the displayed frames, decided when they are
+/// rendered so that later changes to the frames do not matter.
+#[derive(Debug)]
+struct PendingClear {
+    /// Clears the displayed frames.
+    clear: String,
+    /// Moves to the start of the displayed frames to print
+    /// over them, only with [`RenderMode::Overwrite`].
+    rewind: Option<String>,
+}
+
+/// Output passed to the internal loop callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Output {
+    Clear {
+        flush: bool,
+    },
+    Frames {
+        flush: bool,
+    },
+    /// State changes printed with [`TermMode::JsonLines`]
+    /// or [`TermMode::GithubActions`].
+    Events,
+}
+
 /// Run the loop with the given callback.
 ///
 /// # Example
@@ -25,17 +623,23 @@ use crate::{
 /// );
 ///
 /// // Run the loop while blocking the current thread.
-/// l.run(|out| print!("{out}"));
+/// l.run(|out| {
+///     print!("{out}");
+///     Ok(())
+/// });
 /// ```
 #[derive(Debug)]
 pub struct Loop<F: Frames> {
     inner: Arc<Mutex<LoopInner<F>>>,
+    /// Wakes the loop up before the interval elapses.
+    wake: Arc<Condvar>,
 }
 
 impl<F: Frames> Clone for Loop<F> {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            wake: self.wake.clone(),
         }
     }
 }
@@ -49,15 +653,53 @@ impl<F: Frames> Loop<F> {
             inner: Arc::new(Mutex::new(LoopInner {
                 running: false,
                 stop: false,
+                paused: false,
+                displayed: false,
                 auto_stop: true,
+                hide_cursor: false,
                 reset: false,
+                invalidation: None,
+                mode: RenderMode::default(),
+                flush_policy: FlushPolicy::default(),
+                restart_policy: RestartPolicy::default(),
+                on_disconnect: None,
+                status_report: StatusReport::default(),
+                status_export: None,
+                announcer: None,
+                events: None,
+                watchdog: None,
+                timing: None,
+                frame_count: 0,
+                last_output: None,
+                last_status: None,
+                pending_clear: None,
+                snapshot: Snapshot::default(),
+                next_poll: None,
+                printed_width: 0,
+                #[cfg(all(feature = "timerfd", target_os = "linux"))]
+                timer: None,
+                resize_guard: true,
+                negotiate_interval: false,
+                term_width: None,
+                ticks: 0,
+                last_render: None,
                 delay: interval,
                 wait: None,
+                focused: true,
+                background_policy: BackgroundPolicy::default(),
+                term_mode: TermMode::default(),
                 frames,
             })),
+            wake: Arc::new(Condvar::new()),
         }
     }
 
+    /// Create a builder that configures the loop
+    /// before it is shared or started.
+    pub fn builder(frames: F) -> LoopBuilder<F> {
+        LoopBuilder::new(frames)
+    }
+
     /// Run the loop with the given callback.
     ///
     /// # Example
@@ -73,105 +715,534 @@ impl<F: Frames> Loop<F> {
     /// );
     ///
     /// // Run the loop while blocking the current thread.
-    /// l.run(|out| print!("{out}"));
+    /// l.run(|out| {
+    ///     print!("{out}");
+    ///     Ok(())
+    /// });
     /// ```
     #[allow(clippy::missing_errors_doc)]
     pub fn run(
         &self,
         mut f: impl FnMut(&dyn core::fmt::Display) -> io::Result<()>,
     ) -> io::Result<()> {
-        let mut first = true;
-        let mut shared_lock = None;
-        self.inner.lock().unwrap().stop = false;
-        self.inner.lock().unwrap().running = true;
+        self.run_impl(false, |out, _| f(out))
+    }
+
+    fn run_impl(
+        &self,
+        lock_stdout: bool,
+        mut f: impl FnMut(&dyn core::fmt::Display, Output) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let term_mode = self.lock_inner().term_mode;
+        let mut printed_width: usize = 0;
+        let mut f = move |out: &dyn core::fmt::Display, output: Output| {
+            write_mode(term_mode, &mut printed_width, &mut f, out, output)
+        };
+
+        let hide_cursor = {
+            let mut inner = self.lock_inner();
+            inner.stop = false;
+            inner.running = true;
+            inner.frame_count = 0;
+            inner.last_output = None;
+            inner.last_status = None;
+
+            if inner.ticks > 0 && inner.restart_policy == RestartPolicy::Reset {
+                inner.reset = true;
+            }
+
+            inner.hide_cursor
+        };
+
+        let mut res = Ok(());
+        if hide_cursor {
+            res = f(&CursorVisible(false), Output::Clear { flush: false });
+        }
+        res = res.and_then(|()| self.run_cycles(lock_stdout, &mut f));
+        if hide_cursor {
+            let shown = f(&CursorVisible(true), Output::Clear { flush: true });
+            res = res.and(shown);
+        }
+
+        let on_disconnect = {
+            let mut inner = self.lock_inner();
+            inner.running = false;
+
+            match &res {
+                Err(err) if is_disconnect(err) => inner.on_disconnect.take(),
+                _ => None,
+            }
+        };
+
+        // The callback is invoked without holding the lock,
+        // so that it can use the loop.
+        if let Some(mut on_disconnect) = on_disconnect {
+            on_disconnect();
+            self.lock_inner().on_disconnect.get_or_insert(on_disconnect);
+        }
+
+        res
+    }
+
+    fn run_cycles(
+        &self,
+        lock_stdout: bool,
+        mut f: impl FnMut(&dyn core::fmt::Display, Output) -> io::Result<()>,
+    ) -> io::Result<()> {
         loop {
-            let mut inner = self.inner.lock().unwrap();
+            // Stdout is locked before the loop like in `Loop::with_stdout`,
+            // and the loop is not locked while writing, so that a slow
+            // stream does not block its other users.
+            let stdout = lock_stdout.then(|| io::stdout().lock());
+            let mut inner = self.lock_inner();
 
             if (inner.auto_stop && Arc::strong_count(&self.inner) == 1) || inner.stop {
+                let clear = inner
+                    .last_status
+                    .as_ref()
+                    .and_then(|_| inner.status_report.clear());
+                drop(inner);
+                if let Some(clear) = clear {
+                    f(&clear, Output::Clear { flush: true })?;
+                }
                 break;
             }
 
+            let background = inner.is_background();
+            if inner.is_paused(background) {
+                let snapshot = inner.clear_paused();
+                if !snapshot.writes.is_empty() {
+                    drop(inner);
+                    snapshot.write(&mut f)?;
+                    inner = self.lock_inner();
+                }
+                inner.snapshot = snapshot;
+                drop(stdout);
+
+                let delay = inner.delay;
+                drop(
+                    self.wake
+                        .wait_timeout(inner, delay)
+                        .unwrap_or_else(PoisonError::into_inner),
+                );
+                continue;
+            }
+
             if let Some(wait) = inner.wait.take() {
+                drop(inner);
+                drop(stdout);
                 thread::sleep(wait);
+                continue;
             }
 
-            if inner.reset {
-                inner.reset = false;
-                inner.frames.reset();
-            } else if !first {
-                f(&DisplayFn::new(|f| inner.frames.clear(f)))?;
+            let (delay, snapshot) = inner.render(background)?;
+            let focused = inner.focused;
+
+            drop(inner);
+            let write_start = Instant::now();
+            snapshot.write(&mut f)?;
+            let write = write_start.elapsed();
+            let mut inner = self.lock_inner();
+            inner.finish_write(snapshot, write);
+            drop(stdout);
+
+            // The wake up would have been missed while writing.
+            if inner.stop || inner.paused || (!focused && inner.focused) {
+                continue;
             }
+            drop(
+                self.wake
+                    .wait_timeout(inner, delay)
+                    .unwrap_or_else(PoisonError::into_inner),
+            );
+        }
 
-            first = false;
+        Ok(())
+    }
+
+    /// Perform a single tick on the current thread if one is due
+    /// by the given deadline, and return the time until the next one.
+    ///
+    /// This allows driving the loop cooperatively, e.g. from a game
+    /// loop or an event loop, without sleeping. Usually the deadline
+    /// is the current time, or the end of the current frame of the
+    /// application. The stream is flushed according to the
+    /// [`FlushPolicy`] of the loop.
+    ///
+    /// No tick is performed while the loop is running on a thread.
+    /// [`SharedFrames`](crate::SharedFrames) of the loop must not be
+    /// locked by the current thread while polling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Line, Loop};
+    /// # use std::time::{Duration, Instant};
+    /// let l = Loop::new(Duration::from_millis(100), Line::new(spinner::dots()));
+    /// let mut out = Vec::new();
+    ///
+    /// let next = l.poll(&mut out, Instant::now()).unwrap();
+    /// assert_eq!(l.tick_count(), 1);
+    ///
+    /// // The next tick is not due yet.
+    /// l.poll(&mut out, Instant::now()).unwrap();
+    /// assert_eq!(l.tick_count(), 1);
+    ///
+    /// l.poll(&mut out, Instant::now() + next).unwrap();
+    /// assert_eq!(l.tick_count(), 2);
+    /// ```
+    #[allow(clippy::missing_errors_doc)]
+    pub fn poll(&self, mut stream: impl io::Write, deadline: Instant) -> io::Result<Duration> {
+        let mut inner = self.lock_inner();
+        let now = Instant::now();
 
-            drop(shared_lock.take());
-            // Allow other threads to take the lock.
-            thread::sleep(Duration::from_micros(1));
-            shared_lock = Some(SHARED_LOCK.lock().unwrap());
+        if inner.running {
+            return Ok(inner.delay);
+        }
+        if let Some(next) = inner.next_poll.filter(|&next| next > deadline) {
+            return Ok(next.saturating_duration_since(now));
+        }
 
-            f(&inner.frames)?;
+        let term_mode = inner.term_mode;
+        let mut printed_width = inner.printed_width;
+        let mut f = |out: &dyn core::fmt::Display, output: Output| {
+            write_mode(
+                term_mode,
+                &mut printed_width,
+                &mut |out, output| write_stream(&mut stream, out, output),
+                out,
+                output,
+            )
+        };
 
-            inner.frames.advance();
-            let delay = inner.delay;
+        let background = inner.is_background();
+        let delay = if inner.is_paused(background) {
+            let snapshot = inner.clear_paused();
             drop(inner);
+            snapshot.write(&mut f)?;
+            inner = self.lock_inner();
+            inner.snapshot = snapshot;
+            inner.delay
+        } else if let Some(wait) = inner.wait.take() {
+            wait
+        } else {
+            let (delay, snapshot) = inner.render(background)?;
+            // Concurrent polls skip the tick while it is written.
+            inner.next_poll = Some(now + delay);
+            drop(inner);
+            let write_start = Instant::now();
+            snapshot.write(&mut f)?;
+            let write = write_start.elapsed();
+            inner = self.lock_inner();
+            inner.finish_write(snapshot, write);
+            delay
+        };
+
+        inner.printed_width = printed_width;
+        inner.next_poll = Some(now + delay);
 
-            thread::sleep(delay);
+        #[cfg(all(feature = "timerfd", target_os = "linux"))]
+        if let Some(timer) = &inner.timer {
+            timer.arm(delay)?;
         }
-        self.inner.lock().unwrap().running = false;
 
-        Ok(())
+        Ok(delay)
+    }
+
+    /// The time the next tick is due with [`Loop::poll`],
+    /// `None` if it is due right away.
+    ///
+    /// Event loops can use this as the timeout of their poll.
+    #[must_use]
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.lock_inner().next_poll
+    }
+
+    /// Create a file descriptor that becomes readable when
+    /// the next tick is due with [`Loop::poll`], e.g. for
+    /// registering it with `epoll` or `mio`.
+    ///
+    /// The descriptor is re-armed by every poll and replaces
+    /// previously created ones.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use termspin::{spinner, Line, Loop};
+    /// # use std::{os::fd::AsRawFd, time::{Duration, Instant}};
+    /// let l = Loop::new(Duration::from_millis(100), Line::new(spinner::dots()));
+    /// let timer = l.timer_fd().unwrap();
+    ///
+    /// // Register `timer.as_raw_fd()` for readability,
+    /// // and once it is readable:
+    /// l.poll(std::io::stderr(), Instant::now()).unwrap();
+    /// ```
+    #[cfg(all(feature = "timerfd", target_os = "linux"))]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn timer_fd(&self) -> io::Result<crate::TimerFd> {
+        let timer = crate::TimerFd::new()?;
+        let mut inner = self.lock_inner();
+
+        let delay = inner
+            .next_poll
+            .map(|next| next.saturating_duration_since(Instant::now()))
+            .unwrap_or_default();
+        timer.arm(delay)?;
+
+        inner.timer = Some(timer.clone());
+        Ok(timer)
     }
 
     /// Run the loop outputting frames to the given stream.
+    ///
+    /// The stream is flushed according to the [`FlushPolicy`]
+    /// of the loop.
     #[allow(clippy::missing_errors_doc)]
     pub fn run_stream(&self, mut stream: impl std::io::Write) -> io::Result<()> {
-        self.run(|f| {
-            write!(stream, "{}", f)?;
-            stream.flush()
+        self.run_impl(false, |out, output| write_stream(&mut stream, out, output))
+    }
+
+    /// Run the loop outputting frames to the standard output,
+    /// which is locked for the duration of each write.
+    ///
+    /// Other threads that print with `println!` wait until the
+    /// frames are written instead of interleaving with them,
+    /// use [`Loop::with_stdout`] to print above the frames.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn run_locked_stdout(&self) -> io::Result<()> {
+        self.run_impl(true, |out, output| {
+            write_stream(&mut io::stdout().lock(), out, output)
         })
     }
 
+    /// Call the given function with the standard output locked
+    /// after clearing the printed frames, which are printed
+    /// again below anything the function prints.
+    ///
+    /// The loop cannot write to the standard output in the meantime
+    /// if it is running with [`Loop::run_locked_stdout`]. The loop
+    /// itself is not locked while the function runs, so the function
+    /// can use it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use termspin::{spinner, Line, Loop};
+    /// # use std::{io::Write, thread, time::Duration};
+    /// let l = Loop::new(Duration::from_millis(100), Line::new(spinner::dots()));
+    ///
+    /// let looped = l.clone();
+    /// thread::spawn(move || looped.run_locked_stdout());
+    ///
+    /// l.with_stdout(|out| writeln!(out, "downloaded file.txt")).unwrap();
+    /// ```
+    pub fn with_stdout<R>(&self, f: impl FnOnce(&mut io::StdoutLock<'static>) -> R) -> R {
+        // Stdout is locked before the loop like in `Loop::run_locked_stdout`.
+        let mut stdout = io::stdout().lock();
+        let mut inner = self.lock_inner();
+
+        if inner.displayed && inner.term_mode == TermMode::Ansi {
+            let clear = match inner.pending_clear.take() {
+                Some(pending) => pending.clear,
+                None => DisplayFn::new(|f| clear_root(&inner.frames, f)).to_string(),
+            };
+            let _ = write_stream(&mut stdout, &clear, Output::Clear { flush: false });
+            inner.displayed = false;
+        }
+        drop(inner);
+
+        let res = f(&mut stdout);
+        let _ = io::Write::flush(&mut stdout);
+        drop(stdout);
+
+        self.wake.notify_all();
+        res
+    }
+
     /// A convenience function to clear the given stream.
+    ///
+    /// A stopped loop that is started again will not
+    /// clear the frames again afterwards.
     #[allow(clippy::missing_errors_doc)]
     pub fn clear_stream(&self, mut stream: impl std::io::Write) -> io::Result<()> {
+        let mut inner = self.lock_inner();
         write!(
             stream,
             "{}",
-            DisplayFn::new(|f| self.inner.lock().unwrap().frames.clear(f))
-        )
+            DisplayFn::new(|f| clear_root(&inner.frames, f))
+        )?;
+        inner.displayed = false;
+        Ok(())
     }
 
     /// Spawn the loop on a separate thread,
     /// no-op if the loop is already running.
+    ///
+    /// The loop stops if the stream is disconnected,
+    /// see [`Loop::on_disconnect`], or on any other
+    /// error writing to the stream.
     pub fn spawn_stream<S>(&self, stream: S)
     where
         S: std::io::Write + Send + 'static,
     {
-        if self.inner.lock().unwrap().running {
-            return;
+        self.spawn_thread(stream);
+    }
+
+    /// Spawn the loop like [`Loop::spawn_stream`]
+    /// and return the spawned thread, if any.
+    pub(crate) fn spawn_thread<S>(&self, stream: S) -> Option<thread::JoinHandle<()>>
+    where
+        S: std::io::Write + Send + 'static,
+    {
+        if self.lock_inner().running {
+            return None;
         }
 
         let this = self.clone();
 
-        thread::spawn(move || {
-            this.run_stream(stream).unwrap();
-        });
+        Some(thread::spawn(move || {
+            // Errors end the loop, there is no one to report them to.
+            let _ = this.run_stream(stream);
+        }))
+    }
+
+    /// Spawn the loop on a separate thread with the stream
+    /// wrapped in a buffer of the given capacity,
+    /// no-op if the loop is already running.
+    ///
+    /// Each cycle is written to the stream at once when flushed,
+    /// which avoids tearing on slow connections as long as
+    /// the capacity is large enough for a single cycle.
+    pub fn spawn_buffered<S>(&self, stream: S, capacity: usize)
+    where
+        S: std::io::Write + Send + 'static,
+    {
+        self.spawn_stream(io::BufWriter::with_capacity(capacity, stream));
     }
 
     /// Stop a running loop.
     pub fn stop(&self) {
-        self.inner.lock().unwrap().stop = true;
+        self.lock_inner().stop = true;
+        self.wake.notify_all();
+    }
+
+    /// Pause a running loop, the printed frames
+    /// are cleared on the next cycle.
+    pub fn pause(&self) {
+        self.lock_inner().paused = true;
+        self.wake.notify_all();
+    }
+
+    /// Resume a paused loop.
+    pub fn resume(&self) {
+        self.lock_inner().paused = false;
+        self.wake.notify_all();
+    }
+
+    /// Return a type-erased handle for controlling the loop.
+    ///
+    /// The handle does not keep the loop alive.
+    pub fn handle(&self) -> LoopHandle {
+        let inner: Weak<Mutex<LoopInner<F>>> = Arc::downgrade(&self.inner);
+        LoopHandle {
+            inner,
+            wake: self.wake.clone(),
+        }
+    }
+
+    /// Whether the loop is currently running.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.lock_inner().running
+    }
+
+    /// Whether the loop is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.lock_inner().paused
+    }
+
+    /// The amount of frames rendered by the loop.
+    #[must_use]
+    pub fn tick_count(&self) -> u64 {
+        self.lock_inner().ticks
+    }
+
+    /// The time of the last rendered frame.
+    #[must_use]
+    pub fn last_render_at(&self) -> Option<Instant> {
+        self.lock_inner().last_render
     }
 
     /// Wait for the given duration before the
     /// next cycle.
     pub fn wait(&self, duration: Duration) {
-        self.inner.lock().unwrap().wait = Some(duration);
+        self.lock_inner().wait = Some(duration);
     }
 
     /// Reset the frames of the loop.
     pub fn reset(&self) {
-        self.inner.lock().unwrap().reset = true;
+        self.lock_inner().reset = true;
+    }
+
+    /// Assume that the displayed frames were overwritten
+    /// by other output, the next cycle prints the frames
+    /// without clearing the previous ones.
+    ///
+    /// Useful for recovering after something else has written
+    /// to the terminal without coordinating with the loop.
+    pub fn invalidate(&self) {
+        self.lock_inner().invalidation = Some(Invalidation::Redraw);
+    }
+
+    /// Like [`Loop::invalidate`], but the whole screen is
+    /// erased before the frames are printed again.
+    pub fn invalidate_and_erase(&self) {
+        self.lock_inner().invalidation = Some(Invalidation::Erase);
+    }
+
+    /// Tell the loop whether the terminal has focus,
+    /// the terminal is assumed to be focused initially.
+    ///
+    /// Terminals report focus changes as input after
+    /// [`FocusReporting`](crate::ansi::FocusReporting) is enabled,
+    /// reading the input is up to the application.
+    /// The frames are printed immediately when the focus is gained.
+    pub fn set_focused(&self, focused: bool) {
+        let gained = {
+            let mut inner = self.lock_inner();
+            let gained = focused && !inner.focused;
+            inner.focused = focused;
+            gained
+        };
+
+        if gained {
+            self.wake.notify_all();
+        }
+    }
+
+    /// Whether the terminal has focus according
+    /// to [`Loop::set_focused`].
+    #[must_use]
+    pub fn is_focused(&self) -> bool {
+        self.lock_inner().focused
+    }
+
+    /// Set what the loop does while the terminal is unfocused
+    /// or the process is in the background,
+    /// defaults to [`BackgroundPolicy::Normal`].
+    pub fn background_policy(&self, policy: BackgroundPolicy) {
+        self.lock_inner().background_policy = policy;
+    }
+
+    /// Set the capabilities of the output,
+    /// defaults to [`TermMode::Ansi`].
+    ///
+    /// Use [`TermMode::detect`] to pick the mode based on
+    /// the environment. Changes take effect the next time
+    /// the loop is started.
+    pub fn term_mode(&self, mode: TermMode) {
+        self.lock_inner().term_mode = mode;
     }
 
     /// Clone the inner frames.
@@ -180,7 +1251,7 @@ impl<F: Frames> Loop<F> {
     where
         F: Clone,
     {
-        self.inner.lock().unwrap().frames.clone()
+        self.lock_inner().frames.clone()
     }
 
     /// Exit the running loop if only one instance
@@ -190,21 +1261,785 @@ impl<F: Frames> Loop<F> {
     /// on a separate thread that should exit when
     /// all handles to it go out of scope.
     pub fn auto_stop(&self, stop: bool) {
-        self.inner.lock().unwrap().auto_stop = stop;
+        self.lock_inner().auto_stop = stop;
+    }
+
+    /// Hide the cursor while the loop is running,
+    /// defaults to `false`.
+    ///
+    /// The cursor is shown again when the loop stops.
+    pub fn hide_cursor(&self, hide: bool) {
+        self.lock_inner().hide_cursor = hide;
+    }
+
+    /// Set the way printed frames are replaced,
+    /// defaults to [`RenderMode::Clear`].
+    pub fn render_mode(&self, mode: RenderMode) {
+        self.lock_inner().mode = mode;
+    }
+
+    /// Set a callback that is called when the output
+    /// is disconnected (e.g. broken pipe or closed terminal),
+    /// the loop stops before the callback is called.
+    pub fn on_disconnect(&self, f: impl FnMut() + Send + 'static) {
+        self.lock_inner().on_disconnect = Some(Box::new(f));
+    }
+
+    /// Set how the loop continues after being stopped
+    /// and started again, defaults to [`RestartPolicy::Resume`].
+    pub fn restart_policy(&self, policy: RestartPolicy) {
+        self.lock_inner().restart_policy = policy;
+    }
+
+    /// Set where the progress of the frames is reported
+    /// in addition to printing them, defaults to [`StatusReport::Off`].
+    pub fn status_report(&self, report: StatusReport) {
+        self.lock_inner().status_report = report;
+    }
+
+    /// Announce when labeled frames, e.g. lines with text,
+    /// start, finish or fail as complete sentences on their own
+    /// lines above the frames, defaults to `false`.
+    ///
+    /// Screen readers can announce these sentences
+    /// instead of reading spinner glyphs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, FrameState, Group, Line, Loop};
+    /// # use std::time::Duration;
+    /// let mut group = Group::new();
+    /// group.push(Line::new(spinner::dots()).with_text("Downloading"));
+    ///
+    /// let l = Loop::new(Duration::from_millis(100), group);
+    /// l.announce(true);
+    /// // Prints "Downloading started." once, and later
+    /// // "Downloading finished." when the line is done.
+    /// ```
+    pub fn announce(&self, announce: bool) {
+        self.lock_inner().announcer = announce.then(|| Announcer::new(None));
+    }
+
+    /// Like [`Loop::announce`], but the announcements are
+    /// written to the given stream instead, e.g. to the standard
+    /// output while the frames are printed to the standard error.
+    ///
+    /// With [`TermMode::Plain`] only the announcements are printed.
+    /// The stream is written to along with the frames after they are
+    /// unlocked, errors writing to it are ignored.
+    pub fn announce_to(&self, stream: impl io::Write + Send + 'static) {
+        self.lock_inner().announcer = Some(Announcer::new(Some(Box::new(stream))));
+    }
+
+    /// Call the given function with timing data whenever a tick
+    /// takes longer than the given threshold, e.g. because of a slow
+    /// [`Display`] implementation or a blocked stream.
+    ///
+    /// The function is called on the thread running the loop
+    /// after the output of the tick is written,
+    /// it must not use the loop itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Line, Loop};
+    /// # use std::time::Duration;
+    /// let l = Loop::new(Duration::from_millis(100), Line::new(spinner::dots()));
+    /// l.watchdog(Duration::from_millis(500), |tick| {
+    ///     eprintln!("warning: {tick}");
+    /// });
+    /// ```
+    pub fn watchdog(&self, threshold: Duration, f: impl FnMut(&StalledTick) + Send + 'static) {
+        self.lock_inner().watchdog = Some(Watchdog {
+            threshold,
+            callback: Box::new(f),
+        });
+    }
+
+    /// Export a summary of the frames whenever it changes,
+    /// replacing any previous export.
+    pub fn status_export(&self, export: StatusExport) {
+        self.lock_inner().status_export = Some(export);
+    }
+
+    /// Erase the whole region the frames might occupy
+    /// when the terminal becomes narrower than it was
+    /// when the frames were displayed, defaults to `true`.
+    ///
+    /// Narrowing the terminal re-wraps the printed lines,
+    /// so clearing them line by line would leave garbage behind.
+    /// The erased region is estimated generously, and might
+    /// include some output printed before the frames.
+    pub fn resize_guard(&self, guard: bool) {
+        self.lock_inner().resize_guard = guard;
+    }
+
+    /// Update at the interval preferred by the frames
+    /// instead if they have one, see [`Frames::interval`],
+    /// defaults to `false`.
+    ///
+    /// The interval of the loop is used while paused.
+    pub fn negotiate_interval(&self, negotiate: bool) {
+        self.lock_inner().negotiate_interval = negotiate;
+    }
+
+    /// Set when streams are flushed,
+    /// defaults to [`FlushPolicy::EveryFrame`].
+    pub fn flush_policy(&self, policy: FlushPolicy) {
+        self.lock_inner().flush_policy = policy;
+    }
+
+    /// Lock the state of the loop, recovering it if
+    /// a tick panicked so that the loop keeps working.
+    fn lock_inner(&self) -> MutexGuard<'_, LoopInner<F>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A type-erased handle to a [`Loop`] returned by [`Loop::handle`].
+///
+/// All operations are no-op if the loop no longer exists.
+#[must_use]
+#[derive(Clone)]
+pub struct LoopHandle {
+    inner: Weak<dyn Control>,
+    /// Wakes the loop up so that changes apply right away.
+    wake: Arc<Condvar>,
+}
+
+impl core::fmt::Debug for LoopHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoopHandle")
+            .field("exists", &self.exists())
+            .field("running", &self.is_running())
+            .field("paused", &self.is_paused())
+            .finish()
+    }
+}
+
+impl LoopHandle {
+    /// Whether the loop still exists.
+    #[must_use]
+    pub fn exists(&self) -> bool {
+        self.inner.strong_count() > 0
+    }
+
+    /// Stop the loop, see [`Loop::stop`].
+    pub fn stop(&self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.stop();
+            self.wake.notify_all();
+        }
+    }
+
+    /// Pause the loop, see [`Loop::pause`].
+    pub fn pause(&self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.set_paused(true);
+            self.wake.notify_all();
+        }
+    }
+
+    /// Resume the loop, see [`Loop::resume`].
+    pub fn resume(&self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.set_paused(false);
+            self.wake.notify_all();
+        }
+    }
+
+    /// Whether the loop is currently running.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.inner.upgrade().is_some_and(|inner| inner.is_running())
+    }
+
+    /// Whether the loop is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.inner.upgrade().is_some_and(|inner| inner.is_paused())
     }
 }
 
+trait Control: Send + Sync {
+    fn stop(&self);
+    fn set_paused(&self, paused: bool);
+    fn is_running(&self) -> bool;
+    fn is_paused(&self) -> bool;
+}
+
+impl<F: Frames> Control for Mutex<LoopInner<F>> {
+    fn stop(&self) {
+        self.lock().unwrap_or_else(PoisonError::into_inner).stop = true;
+    }
+
+    fn set_paused(&self, paused: bool) {
+        self.lock().unwrap_or_else(PoisonError::into_inner).paused = paused;
+    }
+
+    fn is_running(&self) -> bool {
+        self.lock().unwrap_or_else(PoisonError::into_inner).running
+    }
+
+    fn is_paused(&self) -> bool {
+        self.lock().unwrap_or_else(PoisonError::into_inner).paused
+    }
+}
+
+/// A collection of loops registered under names,
+/// so that they can be looked up and controlled together.
+///
+/// Registered loops are not kept alive by the registry.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Line, Loop, LoopRegistry};
+/// # use std::time::Duration;
+/// let registry = LoopRegistry::new();
+/// let l = Loop::new(Duration::from_millis(100), Line::new(spinner::dots()));
+///
+/// registry.register("downloads", &l);
+///
+/// // Pause all loops while printing something else.
+/// registry.pause_all();
+/// println!("important message");
+/// registry.resume_all();
+/// ```
+#[derive(Debug, Default)]
+pub struct LoopRegistry {
+    loops: Mutex<BTreeMap<String, LoopHandle>>,
+}
+
+#[allow(clippy::missing_panics_doc)]
+impl LoopRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a loop under the given name,
+    /// replacing any loop with the same name.
+    pub fn register<F: Frames>(&self, name: impl Into<String>, l: &Loop<F>) {
+        self.register_handle(name, l.handle());
+    }
+
+    /// Register a loop handle under the given name,
+    /// replacing any loop with the same name.
+    pub fn register_handle(&self, name: impl Into<String>, handle: LoopHandle) {
+        let mut loops = self.loops.lock().unwrap();
+        loops.retain(|_, handle| handle.exists());
+        loops.insert(name.into(), handle);
+    }
+
+    /// Remove the loop with the given name.
+    pub fn unregister(&self, name: &str) -> Option<LoopHandle> {
+        self.loops.lock().unwrap().remove(name)
+    }
+
+    /// Return the loop with the given name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<LoopHandle> {
+        self.loops
+            .lock()
+            .unwrap()
+            .get(name)
+            .filter(|handle| handle.exists())
+            .cloned()
+    }
+
+    /// Return the names of all registered loops that still exist.
+    #[must_use]
+    pub fn names(&self) -> Vec<String> {
+        self.loops
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, handle)| handle.exists())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Stop all registered loops.
+    pub fn stop_all(&self) {
+        self.loops
+            .lock()
+            .unwrap()
+            .values()
+            .for_each(LoopHandle::stop);
+    }
+
+    /// Pause all registered loops.
+    pub fn pause_all(&self) {
+        self.loops
+            .lock()
+            .unwrap()
+            .values()
+            .for_each(LoopHandle::pause);
+    }
+
+    /// Resume all registered loops.
+    pub fn resume_all(&self) {
+        self.loops
+            .lock()
+            .unwrap()
+            .values()
+            .for_each(LoopHandle::resume);
+    }
+}
+
+/// Erase the displayed frames after the terminal
+/// shrank from `prev` to `now` columns.
+///
+/// Every printed line that fit in the previous width
+/// occupies at most `prev / now` rows (rounded up) now.
+fn erase_rewrapped(
+    frames: &dyn Frames,
+    prev: usize,
+    now: usize,
+    f: &mut std::fmt::Formatter<'_>,
+) -> std::fmt::Result {
+    let rows = frames
+        .total_lines()
+        .max(1)
+        .saturating_mul(prev.div_ceil(now.max(1)));
+
+    CursorUp(rows).fmt(f)?;
+    "\r".fmt(f)?;
+    ClearBelow.fmt(f)
+}
+
+/// Clear the frames of a loop, the cursor is on their last row
+/// since they are displayed without a trailing new line.
+fn clear_root(frames: &dyn Frames, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    CursorUp(frames.displayed_lines().saturating_sub(1)).fmt(f)?;
+    frames.clear(f)
+}
+
+/// Move to the start of the frames of a loop, see [`clear_root`].
+fn rewind_root(frames: &dyn Frames, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    CursorUp(frames.displayed_lines().saturating_sub(1)).fmt(f)?;
+    frames.rewind(f)
+}
+
+/// Whether state changes are printed instead of the frames.
+fn prints_events(mode: TermMode) -> bool {
+    matches!(mode, TermMode::JsonLines | TermMode::GithubActions)
+}
+
+/// Whether the error means that the output
+/// is no longer available.
+fn is_disconnect(err: &io::Error) -> bool {
+    // EIO is returned after the terminal has been closed.
+    const EIO: i32 = 5;
+
+    matches!(
+        err.kind(),
+        io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::WriteZero
+            | io::ErrorKind::UnexpectedEof
+    ) || (cfg!(unix) && err.raw_os_error() == Some(EIO))
+}
+
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug)]
 struct LoopInner<F>
 where
     F: Frames,
 {
     running: bool,
     stop: bool,
+    paused: bool,
+    /// Whether frames are displayed that were not cleared.
+    displayed: bool,
     auto_stop: bool,
+    hide_cursor: bool,
     reset: bool,
+    invalidation: Option<Invalidation>,
+    mode: RenderMode,
+    flush_policy: FlushPolicy,
+    restart_policy: RestartPolicy,
+    on_disconnect: Option<Box<dyn FnMut() + Send>>,
+    status_report: StatusReport,
+    status_export: Option<StatusExport>,
+    announcer: Option<Announcer>,
+    /// Tracks state changes for [`TermMode::JsonLines`]
+    /// and [`TermMode::GithubActions`].
+    events: Option<Announcer>,
+    watchdog: Option<Watchdog>,
+    /// The timing of the last tick until it is written.
+    timing: Option<StalledTick>,
+    frame_count: usize,
+    /// The last output with [`FlushPolicy::OnChange`].
+    last_output: Option<String>,
+    last_status: Option<String>,
+    /// How to replace the displayed frames.
+    pending_clear: Option<PendingClear>,
+    /// The reused output buffer.
+    snapshot: Snapshot,
+    next_poll: Option<Instant>,
+    /// The width printed with [`TermMode::CarriageReturn`] by [`Loop::poll`].
+    printed_width: usize,
+    #[cfg(all(feature = "timerfd", target_os = "linux"))]
+    timer: Option<crate::TimerFd>,
+    resize_guard: bool,
+    negotiate_interval: bool,
+    /// The terminal width when the frames were last displayed.
+    term_width: Option<usize>,
+    ticks: u64,
+    last_render: Option<Instant>,
     delay: Duration,
     wait: Option<Duration>,
+    focused: bool,
+    background_policy: BackgroundPolicy,
+    term_mode: TermMode,
     frames: F,
 }
+
+impl<F> LoopInner<F>
+where
+    F: Frames,
+{
+    fn is_paused(&self, background: bool) -> bool {
+        self.paused || (background && self.background_policy == BackgroundPolicy::Pause)
+    }
+
+    /// Clear the displayed frames while paused,
+    /// returns the output to write.
+    fn clear_paused(&mut self) -> Snapshot {
+        let mut snapshot = std::mem::take(&mut self.snapshot);
+        snapshot.clear();
+
+        if self.displayed && self.invalidation.is_none() {
+            match self.pending_clear.take() {
+                Some(pending) => snapshot.push(&pending.clear, Output::Clear { flush: true }),
+                None => snapshot.push(
+                    &DisplayFn::new(|f| clear_root(&self.frames, f)),
+                    Output::Clear { flush: true },
+                ),
+            }
+            self.displayed = false;
+        }
+
+        snapshot
+    }
+
+    /// Render a tick into a snapshot while shared frames are locked,
+    /// returns the delay until the next tick.
+    fn render(&mut self, background: bool) -> io::Result<(Duration, Snapshot)> {
+        let _shared_lock = SHARED_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut snapshot = std::mem::take(&mut self.snapshot);
+        snapshot.clear();
+        let delay = self.tick(&mut snapshot, background)?;
+
+        Ok((delay, snapshot))
+    }
+
+    /// Clear the displayed frames, print the next ones and advance
+    /// them, returns the delay until the next tick.
+    fn tick(&mut self, snapshot: &mut Snapshot, background: bool) -> io::Result<Duration> {
+        let tick_start = Instant::now();
+        let shrunk = self.check_resize();
+
+        // Unchanged frames are left on the screen as they are.
+        let unchanged =
+            self.displayed && self.invalidation.is_none() && shrunk.is_none() && !self.reset;
+        if unchanged
+            && self.announcer.is_none()
+            && !prints_events(self.term_mode)
+            && self.status_report == StatusReport::Off
+            && !self.frames.is_dirty()
+        {
+            self.frame_count = self.frame_count.wrapping_add(1);
+            self.ticks += 1;
+            let rendered = Instant::now();
+            self.last_render = Some(rendered);
+
+            self.frames.advance_at(rendered);
+            self.record_timing(tick_start, tick_start, rendered);
+            return Ok(self.next_delay(background));
+        }
+
+        self.clear_displayed(shrunk, &mut |out, output| {
+            snapshot.push(out, output);
+            Ok(())
+        })?;
+        let cleared = Instant::now();
+
+        if self.reset {
+            self.reset = false;
+            self.frames.reset();
+        }
+
+        let flush = match self.flush_policy {
+            FlushPolicy::EveryFrame => true,
+            FlushPolicy::EveryNFrames(n) => self.frame_count.is_multiple_of(n.max(1)),
+            // Decided once the frames are rendered.
+            FlushPolicy::Never | FlushPolicy::OnChange => false,
+        };
+        self.frame_count = self.frame_count.wrapping_add(1);
+
+        self.export_status(snapshot);
+        let announced = self.announce(snapshot);
+        if prints_events(self.term_mode) {
+            let mode = self.term_mode;
+            let events = match &mut self.events {
+                Some(events) if events.events == Some(mode) => events,
+                events => events.insert(Announcer::events(mode)),
+            }
+            .announcements(&self.frames);
+            if !events.is_empty() {
+                snapshot.push(&events, Output::Events);
+            }
+        }
+
+        let status = self.status_report.report(&self.frames);
+        let status_changed = status.is_some() && status != self.last_status;
+        if status_changed {
+            self.last_status.clone_from(&status);
+        }
+        let flush = flush || status_changed || !announced.is_empty();
+        let status = if status_changed { status } else { None };
+        let status = status.as_deref().unwrap_or_default();
+
+        let overwrite = self.mode == RenderMode::Overwrite;
+        let repaint = overwrite && unchanged && announced.is_empty();
+        snapshot.push_with(Output::Frames { flush }, |buf| {
+            buf.push_str(status);
+            buf.push_str(&announced);
+            if repaint {
+                self.frames.repaint(buf);
+            } else {
+                self.frames.render(buf);
+            }
+            if overwrite {
+                let _ = write!(buf, "{ClearBelow}");
+            }
+        });
+        if self.flush_policy == FlushPolicy::OnChange {
+            if let Some((range, Output::Frames { flush })) = snapshot.writes.last_mut() {
+                let output = &snapshot.buf[range.clone()];
+                if self.last_output.as_deref() != Some(output) {
+                    let last = self.last_output.get_or_insert_with(String::new);
+                    last.clear();
+                    last.push_str(output);
+                    *flush = true;
+                }
+            }
+        }
+
+        self.displayed = true;
+        self.ticks += 1;
+        let rendered = Instant::now();
+        self.last_render = Some(rendered);
+        self.frames.mark_clean();
+
+        // Shared frames can change once the lock is released and
+        // advancing can remove children, so how to clear the
+        // displayed frames is decided right away.
+        self.pending_clear = Some(PendingClear {
+            clear: DisplayFn::new(|f| clear_root(&self.frames, f)).to_string(),
+            rewind: (self.mode == RenderMode::Overwrite)
+                .then(|| DisplayFn::new(|f| rewind_root(&self.frames, f)).to_string()),
+        });
+
+        self.frames.advance_at(rendered);
+        self.record_timing(tick_start, cleared, rendered);
+
+        Ok(self.next_delay(background))
+    }
+
+    /// The delay until the next tick.
+    fn next_delay(&self, background: bool) -> Duration {
+        let delay = if self.negotiate_interval {
+            self.frames.interval().unwrap_or(self.delay)
+        } else {
+            self.delay
+        };
+        match self.background_policy {
+            BackgroundPolicy::Throttle(interval) if background => delay.max(interval),
+            _ => delay,
+        }
+    }
+
+    /// Announce state changes, returns the announcements
+    /// to print with the frames.
+    ///
+    /// Announcements for a separate stream are recorded
+    /// for the snapshot to write instead.
+    fn announce(&mut self, snapshot: &mut Snapshot) -> String {
+        let Some(announcer) = &mut self.announcer else {
+            return String::new();
+        };
+
+        let sentences = announcer.announcements(&self.frames);
+        let Some(stream) = &announcer.stream else {
+            // Clear leftovers of overwritten frames after each line.
+            return sentences.replace('\n', &format!("{ClearLineEnd}\n"));
+        };
+
+        if !sentences.is_empty() {
+            snapshot.announcements = Some((stream.clone(), sentences));
+        }
+
+        String::new()
+    }
+
+    /// Record the timing of the tick for the watchdog,
+    /// reported once the output is written.
+    fn record_timing(&mut self, start: Instant, cleared: Instant, rendered: Instant) {
+        if self.watchdog.is_none() {
+            return;
+        }
+
+        let end = Instant::now();
+        self.timing = Some(StalledTick {
+            tick: self.ticks,
+            interval: self.delay,
+            clear: cleared - start,
+            render: rendered - cleared,
+            advance: end - rendered,
+            write: Duration::ZERO,
+        });
+    }
+
+    /// Keep the written snapshot for reuse and report the
+    /// tick to the watchdog if it took too long.
+    fn finish_write(&mut self, snapshot: Snapshot, write: Duration) {
+        self.snapshot = snapshot;
+
+        let (Some(watchdog), Some(mut tick)) = (&mut self.watchdog, self.timing.take()) else {
+            return;
+        };
+        tick.write = write;
+
+        if tick.total() > watchdog.threshold {
+            (watchdog.callback)(&tick);
+        }
+    }
+
+    /// Record the status summary for the snapshot
+    /// to write if it changed.
+    fn export_status(&mut self, snapshot: &mut Snapshot) {
+        if let Some(export) = &mut self.status_export {
+            snapshot.export = export
+                .update(&self.frames)
+                .map(|summary| (export.target.clone(), summary));
+        }
+    }
+
+    /// Whether nobody is watching the frames,
+    /// only checked if there is a background policy.
+    fn is_background(&self) -> bool {
+        self.background_policy != BackgroundPolicy::Normal
+            && (!self.focused || term::is_foreground() == Some(false))
+    }
+
+    /// Remember the width of the terminal, returns the previous
+    /// and the current width if the terminal narrowed.
+    fn check_resize(&mut self) -> Option<(usize, usize)> {
+        let width = if self.resize_guard {
+            term::width()
+        } else {
+            None
+        };
+        let shrunk = match (self.term_width, width) {
+            (Some(prev), Some(now)) if now < prev => Some((prev, now)),
+            _ => None,
+        };
+        if width.is_some() {
+            self.term_width = width;
+        }
+        shrunk
+    }
+
+    /// Remove the displayed frames before printing the next ones,
+    /// or erase them if the terminal narrowed.
+    fn clear_displayed(
+        &mut self,
+        shrunk: Option<(usize, usize)>,
+        f: &mut impl FnMut(&dyn core::fmt::Display, Output) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let pending = self.pending_clear.take();
+
+        if let Some(invalidation) = self.invalidation.take() {
+            if invalidation == Invalidation::Erase {
+                f(&EraseDisplay, Output::Clear { flush: false })?;
+            }
+        } else if let (true, Some((prev, now))) = (self.displayed, shrunk) {
+            f(
+                &DisplayFn::new(|f| erase_rewrapped(&self.frames, prev, now, f)),
+                Output::Clear { flush: false },
+            )?;
+        } else if let (true, Some(pending)) = (self.displayed, pending) {
+            let replace = pending.rewind.as_ref().unwrap_or(&pending.clear);
+            f(replace, Output::Clear { flush: false })?;
+        } else if self.displayed {
+            match self.mode {
+                RenderMode::Clear => {
+                    f(
+                        &DisplayFn::new(|f| clear_root(&self.frames, f)),
+                        Output::Clear { flush: false },
+                    )?;
+                }
+                RenderMode::Overwrite => {
+                    f(
+                        &DisplayFn::new(|f| rewind_root(&self.frames, f)),
+                        Output::Clear { flush: false },
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<F> core::fmt::Debug for LoopInner<F>
+where
+    F: Frames + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoopInner")
+            .field("running", &self.running)
+            .field("stop", &self.stop)
+            .field("paused", &self.paused)
+            .field("displayed", &self.displayed)
+            .field("auto_stop", &self.auto_stop)
+            .field("hide_cursor", &self.hide_cursor)
+            .field("reset", &self.reset)
+            .field("invalidation", &self.invalidation)
+            .field("mode", &self.mode)
+            .field("flush_policy", &self.flush_policy)
+            .field("restart_policy", &self.restart_policy)
+            .field("status_report", &self.status_report)
+            .field("status_export", &self.status_export)
+            .field("announce", &self.announcer.is_some())
+            .field("events", &self.events.is_some())
+            .field("watchdog", &self.watchdog.as_ref().map(|w| w.threshold))
+            .field("timing", &self.timing)
+            .field("frame_count", &self.frame_count)
+            .field("last_output", &self.last_output)
+            .field("last_status", &self.last_status)
+            .field("pending_clear", &self.pending_clear)
+            .field("snapshot", &self.snapshot)
+            .field("next_poll", &self.next_poll)
+            .field("printed_width", &self.printed_width)
+            .field("resize_guard", &self.resize_guard)
+            .field("negotiate_interval", &self.negotiate_interval)
+            .field("term_width", &self.term_width)
+            .field("ticks", &self.ticks)
+            .field("last_render", &self.last_render)
+            .field("delay", &self.delay)
+            .field("wait", &self.wait)
+            .field("focused", &self.focused)
+            .field("background_policy", &self.background_policy)
+            .field("term_mode", &self.term_mode)
+            .field("frames", &self.frames)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,1170 @@
+//! Progress bar [`Frames`] and utilities.
+
+use std::{
+    borrow::Cow,
+    fmt::Write,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    time::DurationFormat, util::DisplayFn, FrameState, Frames, Line, SharedFrames, TypedGroup,
+};
+
+mod io;
+
+pub use io::{ProgressReader, ProgressWriter, ReadProgressExt, WriteProgressExt};
+
+/// The appearance of a progress bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarStyle {
+    /// The amount of characters the bar consists of.
+    pub width: usize,
+    /// The character of the completed part.
+    pub filled: char,
+    /// The character of the remaining part.
+    pub empty: char,
+}
+
+impl Default for BarStyle {
+    fn default() -> Self {
+        Self {
+            width: 20,
+            filled: '█',
+            empty: '░',
+        }
+    }
+}
+
+impl BarStyle {
+    /// Display a bar with the given fraction completed,
+    /// the fraction is clamped between `0.0` and `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::progress::BarStyle;
+    /// let style = BarStyle {
+    ///     width: 4,
+    ///     filled: '#',
+    ///     empty: '-',
+    /// };
+    ///
+    /// assert_eq!(style.display(0.5).to_string(), "##--");
+    /// ```
+    #[must_use]
+    pub fn display(self, fraction: f64) -> impl core::fmt::Display {
+        DisplayFn::new(move |f| {
+            let filled = fraction_of(self.width, fraction);
+
+            for _ in 0..filled {
+                f.write_char(self.filled)?;
+            }
+            for _ in filled..self.width {
+                f.write_char(self.empty)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl BarStyle {
+    /// Display an indeterminate bar with a block
+    /// that moves back and forth with each tick.
+    #[must_use]
+    pub fn display_indeterminate(self, tick: usize) -> impl core::fmt::Display {
+        DisplayFn::new(move |f| {
+            let block = (self.width / 4).max(1).min(self.width);
+            let span = self.width - block;
+            let start = if span == 0 {
+                0
+            } else {
+                let pos = tick % (2 * span);
+                pos.min(2 * span - pos)
+            };
+
+            for i in 0..self.width {
+                if (start..start + block).contains(&i) {
+                    f.write_char(self.filled)?;
+                } else {
+                    f.write_char(self.empty)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// The given fraction of the amount, rounded down.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn fraction_of(amount: usize, fraction: f64) -> usize {
+    let fraction = if fraction.is_nan() {
+        0.0
+    } else {
+        fraction.clamp(0.0, 1.0)
+    };
+
+    ((amount as f64) * fraction).floor() as usize
+}
+
+/// Estimates the rate of progress with an exponential
+/// moving average, smoothing out bursts and stalls.
+///
+/// Samples are weighted by the time passed since the previous one,
+/// so the estimate does not depend on how often it is updated.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::progress::RateEstimator;
+/// # use std::time::{Duration, Instant};
+/// let mut rate = RateEstimator::new(Duration::from_secs(5));
+/// let start = Instant::now();
+///
+/// rate.update_at(0, start);
+/// rate.update_at(100, start + Duration::from_secs(1));
+/// assert_eq!(rate.rate(), Some(100.0));
+///
+/// assert_eq!(rate.eta(200), Some(Duration::from_secs(2)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RateEstimator {
+    window: Duration,
+    rate: Option<f64>,
+    last: Option<(Instant, u64)>,
+}
+
+impl Default for RateEstimator {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5))
+    }
+}
+
+impl RateEstimator {
+    /// Create an estimator that smooths over roughly
+    /// the given window of time, defaults to 5 seconds.
+    ///
+    /// Shorter windows react faster to changes.
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            rate: None,
+            last: None,
+        }
+    }
+
+    /// Record the current position.
+    pub fn update(&mut self, position: u64) -> &mut Self {
+        self.update_at(position, Instant::now())
+    }
+
+    /// Record the position at the given time.
+    ///
+    /// A position lower than the previous one resets the estimate.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn update_at(&mut self, position: u64, now: Instant) -> &mut Self {
+        let Some((last_time, last_position)) = self.last else {
+            self.last = Some((now, position));
+            return self;
+        };
+
+        if position < last_position {
+            self.reset();
+            self.last = Some((now, position));
+            return self;
+        }
+
+        let dt = now.saturating_duration_since(last_time).as_secs_f64();
+        if dt <= 0.0 {
+            return self;
+        }
+
+        let sample = (position - last_position) as f64 / dt;
+        self.rate = Some(match self.rate {
+            Some(rate) => {
+                let window = self.window.as_secs_f64().max(f64::EPSILON);
+                let alpha = 1.0 - (-dt / window).exp();
+                rate + alpha * (sample - rate)
+            }
+            None => sample,
+        });
+        self.last = Some((now, position));
+
+        self
+    }
+
+    /// The estimated rate per second, if there were
+    /// enough samples.
+    #[must_use]
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// The estimated time to progress the given amount,
+    /// if the rate is known and positive.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn eta(&self, remaining: u64) -> Option<Duration> {
+        let rate = self.rate.filter(|&rate| rate > 0.0)?;
+        Duration::try_from_secs_f64(remaining as f64 / rate).ok()
+    }
+
+    /// Forget all samples.
+    pub fn reset(&mut self) -> &mut Self {
+        self.rate = None;
+        self.last = None;
+        self
+    }
+}
+
+/// A position that can be advanced from many threads
+/// without locking, see [`Progress::counter`].
+///
+/// # Example
+///
+/// ```
+/// # use termspin::progress::Progress;
+/// # use std::thread;
+/// let mut progress = Progress::new().with_total(Some(400));
+/// let counter = progress.counter();
+///
+/// thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             for _ in 0..100 {
+///                 counter.inc(1);
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(progress.position(), 400);
+/// ```
+#[derive(Debug, Default)]
+pub struct ProgressCounter {
+    position: AtomicU64,
+}
+
+impl ProgressCounter {
+    /// Create a counter at the given position.
+    #[must_use]
+    pub fn new(position: u64) -> Self {
+        Self {
+            position: AtomicU64::new(position),
+        }
+    }
+
+    /// Increment the position.
+    pub fn inc(&self, delta: u64) {
+        self.position.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    /// Set the position.
+    pub fn set(&self, position: u64) {
+        self.position.store(position, Ordering::Relaxed);
+    }
+
+    /// The current position.
+    #[must_use]
+    pub fn get(&self) -> u64 {
+        self.position.load(Ordering::Relaxed)
+    }
+}
+
+/// The state of a [`Progress`] that can be stored
+/// and restored later, e.g. by resumable downloads.
+///
+/// The checkpoint is serialized with [`Display`](core::fmt::Display)
+/// and parsed with [`FromStr`].
+///
+/// # Example
+///
+/// ```
+/// # use termspin::progress::{Progress, ProgressCheckpoint};
+/// let mut progress = Progress::new().with_total(Some(100));
+/// progress.set_position(42);
+///
+/// let stored = progress.checkpoint().to_string();
+///
+/// // After a restart.
+/// let checkpoint: ProgressCheckpoint = stored.parse().unwrap();
+/// let progress = Progress::from_checkpoint(&checkpoint);
+/// assert_eq!(progress.position(), 42);
+/// assert_eq!(progress.total(), Some(100));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ProgressCheckpoint {
+    /// The position.
+    pub position: u64,
+    /// The total if known.
+    pub total: Option<u64>,
+    /// The time elapsed so far.
+    pub elapsed: Duration,
+    /// The estimated rate per second if known.
+    pub rate: Option<f64>,
+}
+
+impl core::fmt::Display for ProgressCheckpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "position={}", self.position)?;
+        if let Some(total) = self.total {
+            write!(f, " total={total}")?;
+        }
+        write!(f, " elapsed={}", self.elapsed.as_secs_f64())?;
+        if let Some(rate) = self.rate {
+            write!(f, " rate={rate}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ProgressCheckpoint {
+    type Err = ParseCheckpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut checkpoint = ProgressCheckpoint::default();
+
+        for field in s.split_whitespace() {
+            let (key, value) = field.split_once('=').ok_or(ParseCheckpointError)?;
+
+            match key {
+                "position" => checkpoint.position = parse_field(value)?,
+                "total" => checkpoint.total = Some(parse_field(value)?),
+                "elapsed" => {
+                    checkpoint.elapsed = Duration::try_from_secs_f64(parse_field(value)?)
+                        .map_err(|_| ParseCheckpointError)?;
+                }
+                "rate" => checkpoint.rate = Some(parse_field(value)?),
+                // Allow newer fields.
+                _ => {}
+            }
+        }
+
+        Ok(checkpoint)
+    }
+}
+
+fn parse_field<T: FromStr>(value: &str) -> Result<T, ParseCheckpointError> {
+    value.parse().map_err(|_| ParseCheckpointError)
+}
+
+/// The error returned when parsing an invalid [`ProgressCheckpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseCheckpointError;
+
+impl core::fmt::Display for ParseCheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("invalid progress checkpoint")
+    }
+}
+
+impl std::error::Error for ParseCheckpointError {}
+
+/// Progress towards a total that might not be known
+/// in advance, e.g. a download without a known size.
+///
+/// Without a total an indeterminate bar is displayed,
+/// which turns into a regular bar once the total is set.
+/// The elapsed time is displayed after the bar.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::progress::Progress;
+/// let mut progress = Progress::new();
+/// progress.inc(512);
+///
+/// // The total becomes known later.
+/// progress.set_total(Some(2048));
+/// assert_eq!(progress.fraction(), Some(0.25));
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Progress {
+    position: u64,
+    total: Option<u64>,
+    start: Instant,
+    /// The time of the last [`Frames::advance_at`], if any.
+    now: Option<Instant>,
+    tick: usize,
+    style: BarStyle,
+    elapsed_format: Option<DurationFormat>,
+    rate: RateEstimator,
+    counter: Option<Arc<ProgressCounter>>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Progress {
+    /// Create indeterminate progress.
+    pub fn new() -> Self {
+        Self {
+            position: 0,
+            total: None,
+            start: Instant::now(),
+            now: None,
+            tick: 0,
+            style: BarStyle::default(),
+            elapsed_format: Some(DurationFormat::default()),
+            rate: RateEstimator::default(),
+            counter: None,
+        }
+    }
+
+    /// Set the total, `None` makes the progress indeterminate.
+    pub fn with_total(mut self, total: Option<u64>) -> Self {
+        self.total = total;
+        self
+    }
+
+    /// Set the total, `None` makes the progress indeterminate.
+    ///
+    /// The elapsed time and the position are kept.
+    pub fn set_total(&mut self, total: Option<u64>) -> &mut Self {
+        self.total = total;
+        self
+    }
+
+    /// Set the current position.
+    pub fn set_position(&mut self, position: u64) -> &mut Self {
+        match &self.counter {
+            Some(counter) => counter.set(position),
+            None => self.position = position,
+        }
+        self
+    }
+
+    /// Increment the current position.
+    pub fn inc(&mut self, delta: u64) -> &mut Self {
+        match &self.counter {
+            Some(counter) => counter.inc(delta),
+            None => self.position = self.position.saturating_add(delta),
+        }
+        self
+    }
+
+    /// Create progress that continues from the given checkpoint,
+    /// including its elapsed time and rate.
+    pub fn from_checkpoint(checkpoint: &ProgressCheckpoint) -> Self {
+        let mut progress = Self::new().with_total(checkpoint.total);
+        progress.position = checkpoint.position;
+        progress.start = Instant::now()
+            .checked_sub(checkpoint.elapsed)
+            .unwrap_or(progress.start);
+        progress.rate.rate = checkpoint.rate;
+        progress
+    }
+
+    /// The current state that can be restored
+    /// with [`Progress::from_checkpoint`].
+    #[must_use]
+    pub fn checkpoint(&self) -> ProgressCheckpoint {
+        ProgressCheckpoint {
+            position: self.position(),
+            total: self.total,
+            elapsed: self.elapsed(),
+            rate: self.rate(),
+        }
+    }
+
+    /// A counter that holds the position from now on,
+    /// so that it can be advanced from other threads
+    /// without locking the progress.
+    ///
+    /// The same counter is returned on subsequent calls.
+    pub fn counter(&mut self) -> Arc<ProgressCounter> {
+        let position = self.position;
+        self.counter
+            .get_or_insert_with(|| Arc::new(ProgressCounter::new(position)))
+            .clone()
+    }
+
+    /// Set the appearance of the bar.
+    pub fn with_bar_style(mut self, style: BarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the appearance of the bar.
+    pub fn set_bar_style(&mut self, style: BarStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the format of the elapsed time,
+    /// `None` hides it.
+    pub fn with_elapsed_format(mut self, format: Option<DurationFormat>) -> Self {
+        self.elapsed_format = format;
+        self
+    }
+
+    /// Set the format of the elapsed time,
+    /// `None` hides it.
+    pub fn set_elapsed_format(&mut self, format: Option<DurationFormat>) -> &mut Self {
+        self.elapsed_format = format;
+        self
+    }
+
+    /// The current position.
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.counter
+            .as_ref()
+            .map_or(self.position, |counter| counter.get())
+    }
+
+    /// The total if known.
+    #[must_use]
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// The time elapsed since the progress was created or reset.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.now
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.start)
+    }
+
+    /// Set the estimator of the rate, e.g. to change its window.
+    pub fn with_rate_estimator(mut self, rate: RateEstimator) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    /// Set the estimator of the rate, e.g. to change its window.
+    pub fn set_rate_estimator(&mut self, rate: RateEstimator) -> &mut Self {
+        self.rate = rate;
+        self
+    }
+
+    /// The estimated rate of progress per second.
+    ///
+    /// The estimate is updated each time the frames advance.
+    #[must_use]
+    pub fn rate(&self) -> Option<f64> {
+        self.rate.rate()
+    }
+
+    /// The estimated time remaining if the total is known.
+    #[must_use]
+    pub fn eta(&self) -> Option<Duration> {
+        self.rate.eta(self.total?.saturating_sub(self.position()))
+    }
+
+    /// The completed fraction if the total is known.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total? {
+            0 => Some(1.0),
+            total => Some((self.position() as f64 / total as f64).min(1.0)),
+        }
+    }
+
+    /// Turn this progress into [`SharedFrames`].
+    pub fn shared(self) -> SharedFrames<Self> {
+        SharedFrames::new(self)
+    }
+}
+
+impl core::fmt::Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.fraction() {
+            Some(fraction) => write!(
+                f,
+                "{} {:>3}%",
+                self.style.display(fraction),
+                fraction_of(100, fraction)
+            )?,
+            None => self.style.display_indeterminate(self.tick).fmt(f)?,
+        }
+
+        if let Some(format) = self.elapsed_format {
+            write!(f, " {}", format.display(self.elapsed()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Frames for Progress {
+    fn advance(&mut self) {
+        self.tick = self.tick.wrapping_add(1);
+        self.rate.update(self.position());
+        self.now = None;
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.tick = self.tick.wrapping_add(1);
+        self.rate.update_at(self.position(), now);
+        self.now = Some(now);
+    }
+
+    fn reset(&mut self) {
+        self.set_position(0);
+        self.tick = 0;
+        self.start = Instant::now();
+        self.now = None;
+        self.rate.reset();
+    }
+
+    fn state(&self) -> FrameState {
+        match self.total {
+            Some(total) if self.position() >= total => FrameState::Done,
+            _ => FrameState::Running,
+        }
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.fraction()
+    }
+}
+
+/// Progress made up of weighted phases, e.g. downloading,
+/// extracting and verifying, displayed as a single bar.
+///
+/// The bar is followed by the overall percentage
+/// and the name of the current phase.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::progress::PhasedProgress;
+/// let mut progress = PhasedProgress::new()
+///     .with_phase("download", 70.0)
+///     .with_phase("extract", 20.0)
+///     .with_phase("verify", 10.0);
+///
+/// progress.set_progress(0.5);
+/// assert!((progress.fraction() - 0.35).abs() < f64::EPSILON);
+///
+/// progress.next_phase();
+/// assert!((progress.fraction() - 0.7).abs() < f64::EPSILON);
+/// ```
+#[must_use]
+#[derive(Debug, Default, Clone)]
+pub struct PhasedProgress {
+    phases: Vec<Phase>,
+    idx: usize,
+    style: BarStyle,
+}
+
+#[derive(Debug, Clone)]
+struct Phase {
+    name: Cow<'static, str>,
+    weight: f64,
+    progress: f64,
+}
+
+impl PhasedProgress {
+    /// Create progress without any phases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a phase with the given relative weight.
+    pub fn with_phase(mut self, name: &str, weight: f64) -> Self {
+        self.add_phase(name, weight);
+        self
+    }
+
+    /// Add a phase with the given relative weight.
+    pub fn add_phase(&mut self, name: &str, weight: f64) -> &mut Self {
+        self.phases.push(Phase {
+            name: name.to_string().into(),
+            weight: weight.max(0.0),
+            progress: 0.0,
+        });
+        self
+    }
+
+    /// Set the appearance of the bar.
+    pub fn with_bar_style(mut self, style: BarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the appearance of the bar.
+    pub fn set_bar_style(&mut self, style: BarStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the completed fraction of the current phase,
+    /// clamped between `0.0` and `1.0`.
+    pub fn set_progress(&mut self, fraction: f64) -> &mut Self {
+        if let Some(phase) = self.phases.get_mut(self.idx) {
+            phase.progress = fraction.clamp(0.0, 1.0);
+        }
+        self
+    }
+
+    /// Complete the current phase and move on to the next one.
+    pub fn next_phase(&mut self) -> &mut Self {
+        if let Some(phase) = self.phases.get_mut(self.idx) {
+            phase.progress = 1.0;
+            self.idx = (self.idx + 1).min(self.phases.len() - 1);
+        }
+        self
+    }
+
+    /// The index of the current phase.
+    #[must_use]
+    pub fn phase(&self) -> usize {
+        self.idx
+    }
+
+    /// The name of the current phase.
+    #[must_use]
+    pub fn phase_name(&self) -> Option<&str> {
+        self.phases.get(self.idx).map(|phase| &*phase.name)
+    }
+
+    /// The overall completed fraction according
+    /// to the weights of the phases.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        let total: f64 = self.phases.iter().map(|phase| phase.weight).sum();
+
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        self.phases
+            .iter()
+            .map(|phase| phase.weight * phase.progress)
+            .sum::<f64>()
+            / total
+    }
+}
+
+impl core::fmt::Display for PhasedProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fraction = self.fraction();
+
+        write!(
+            f,
+            "{} {:>3}%",
+            self.style.display(fraction),
+            fraction_of(100, fraction)
+        )?;
+
+        match self.phase_name() {
+            Some(name) if !name.is_empty() => write!(f, " {name}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Frames for PhasedProgress {
+    fn advance(&mut self) {}
+
+    fn reset(&mut self) {
+        self.idx = 0;
+
+        for phase in &mut self.phases {
+            phase.progress = 0.0;
+        }
+    }
+
+    fn state(&self) -> FrameState {
+        if !self.phases.is_empty() && self.phases.iter().all(|phase| phase.progress >= 1.0) {
+            FrameState::Done
+        } else {
+            FrameState::Running
+        }
+    }
+
+    fn progress(&self) -> Option<f64> {
+        Some(self.fraction())
+    }
+}
+
+/// A preset for transferring multiple files, e.g. for sync
+/// or upload tools.
+///
+/// The progress of the current file is displayed above a
+/// pinned bar of the overall progress with the amount
+/// of finished and failed files.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::progress::Transfer;
+/// let mut transfer = Transfer::new(2, Some(300));
+///
+/// transfer.start_file("a.txt", Some(100));
+/// transfer.inc(100);
+/// transfer.finish_file();
+///
+/// transfer.start_file("b.txt", Some(200));
+/// transfer.inc(50);
+/// transfer.fail_file();
+///
+/// assert_eq!(transfer.finished_files(), 1);
+/// assert_eq!(transfer.failed_files(), 1);
+/// ```
+#[must_use]
+pub struct Transfer {
+    group: TypedGroup<Line>,
+    has_file: bool,
+    files: usize,
+    finished: usize,
+    failed: usize,
+}
+
+impl Transfer {
+    /// Create a transfer of the given amount of files and bytes.
+    pub fn new(files: usize, total_bytes: Option<u64>) -> Self {
+        let mut group = TypedGroup::new();
+        group.push(Line::new(Progress::new().with_total(total_bytes)));
+
+        let mut transfer = Self {
+            group,
+            has_file: false,
+            files,
+            finished: 0,
+            failed: 0,
+        };
+        transfer.update_summary();
+        transfer
+    }
+
+    /// Start transferring a file, replacing the
+    /// progress of the previous file.
+    pub fn start_file(&mut self, name: &str, size: Option<u64>) -> &mut Self {
+        let line = Line::new(Progress::new().with_total(size)).with_text(name);
+
+        if self.has_file {
+            if let Some(current) = self.group.get_mut(0) {
+                *current = line;
+            }
+        } else {
+            self.group.insert(0, line);
+            self.has_file = true;
+        }
+
+        self
+    }
+
+    /// Record transferred bytes of the current file.
+    pub fn inc(&mut self, bytes: u64) -> &mut Self {
+        for line in self.group.iter_mut() {
+            if let Some(progress) = line.spinner_as_mut::<Progress>() {
+                progress.inc(bytes);
+            }
+        }
+        self
+    }
+
+    /// Finish the current file successfully.
+    pub fn finish_file(&mut self) -> &mut Self {
+        self.finished += 1;
+        self.end_file();
+        self
+    }
+
+    /// Finish the current file with a failure.
+    pub fn fail_file(&mut self) -> &mut Self {
+        self.failed += 1;
+        self.end_file();
+        self
+    }
+
+    /// The amount of successfully transferred files.
+    #[must_use]
+    pub fn finished_files(&self) -> usize {
+        self.finished
+    }
+
+    /// The amount of files that failed.
+    #[must_use]
+    pub fn failed_files(&self) -> usize {
+        self.failed
+    }
+
+    /// The amount of files to transfer.
+    #[must_use]
+    pub fn total_files(&self) -> usize {
+        self.files
+    }
+
+    /// Turn this transfer into [`SharedFrames`].
+    pub fn shared(self) -> SharedFrames<Self> {
+        SharedFrames::new(self)
+    }
+
+    fn end_file(&mut self) {
+        if self.has_file {
+            self.group.try_remove(0);
+            self.has_file = false;
+        }
+        self.update_summary();
+    }
+
+    fn update_summary(&mut self) {
+        let mut summary = format!("{}/{} files", self.finished, self.files);
+        if self.failed > 0 {
+            let _ = write!(summary, ", {} failed", self.failed);
+        }
+
+        let idx = self.group.len() - 1;
+        if let Some(overall) = self.group.get_mut(idx) {
+            overall.set_text(&summary);
+        }
+    }
+}
+
+impl core::fmt::Display for Transfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.group.fmt(f)
+    }
+}
+
+impl Frames for Transfer {
+    fn advance(&mut self) {
+        self.group.advance();
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.group.advance_at(now);
+    }
+
+    fn render(&self, buf: &mut String) {
+        self.group.render(buf);
+    }
+
+    fn repaint(&self, buf: &mut String) {
+        self.group.repaint(buf);
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.group.is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.group.mark_clean();
+    }
+
+    fn reset(&mut self) {
+        self.group.reset();
+    }
+
+    fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.group.clear(f)
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.group.rewind(f)
+    }
+
+    fn total_lines(&self) -> usize {
+        self.group.total_lines()
+    }
+
+    fn lines_changed(&self) -> bool {
+        self.group.lines_changed()
+    }
+
+    fn state(&self) -> FrameState {
+        if self.finished + self.failed < self.files {
+            FrameState::Running
+        } else if self.failed > 0 {
+            FrameState::Failed
+        } else {
+            FrameState::Done
+        }
+    }
+
+    fn progress(&self) -> Option<f64> {
+        let overall = self.group.get(self.group.len() - 1)?;
+        overall.spinner_as::<Progress>()?.fraction()
+    }
+}
+
+/// A preset of two progress bars below a shared header
+/// and above a bar of their combined progress,
+/// e.g. for downloads and uploads of a speed test.
+///
+/// The combined bar is updated when the preset is advanced.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{progress::DualProgress, Frames};
+/// let mut speed = DualProgress::new("speed test", "download", "upload");
+///
+/// speed.first_mut().set_total(Some(100)).inc(50);
+/// speed.second_mut().set_total(Some(100)).inc(100);
+/// speed.advance();
+///
+/// assert_eq!(speed.progress(), Some(0.75));
+/// assert_eq!(speed.total_lines(), 4);
+/// ```
+#[must_use]
+pub struct DualProgress {
+    group: TypedGroup<Line>,
+}
+
+impl DualProgress {
+    /// Create indeterminate progress bars with the given header
+    /// and names.
+    pub fn new(header: &str, first: &str, second: &str) -> Self {
+        let mut group = TypedGroup::new();
+        group.push(Line::new(crate::spinner::empty()).with_text(header));
+        group.push(Line::new(Progress::new()).with_text(first));
+        group.push(Line::new(Progress::new()).with_text(second));
+        group.push(Line::new(Progress::new()).with_text("overall"));
+
+        Self { group }
+    }
+
+    /// Set the header displayed above the bars.
+    pub fn set_header(&mut self, header: &str) -> &mut Self {
+        if let Some(line) = self.group.get_mut(0) {
+            line.set_text(header);
+        }
+        self
+    }
+
+    /// The first progress.
+    pub fn first(&self) -> &Progress {
+        self.progress_at(1)
+    }
+
+    /// The first progress.
+    pub fn first_mut(&mut self) -> &mut Progress {
+        self.progress_at_mut(1)
+    }
+
+    /// The second progress.
+    pub fn second(&self) -> &Progress {
+        self.progress_at(2)
+    }
+
+    /// The second progress.
+    pub fn second_mut(&mut self) -> &mut Progress {
+        self.progress_at_mut(2)
+    }
+
+    /// Turn this progress into [`SharedFrames`].
+    pub fn shared(self) -> SharedFrames<Self> {
+        SharedFrames::new(self)
+    }
+
+    fn progress_at(&self, idx: usize) -> &Progress {
+        self.group
+            .get(idx)
+            .and_then(Line::spinner_as::<Progress>)
+            .expect("the lines of the preset are not replaced")
+    }
+
+    fn progress_at_mut(&mut self, idx: usize) -> &mut Progress {
+        self.group
+            .get_mut(idx)
+            .and_then(Line::spinner_as_mut::<Progress>)
+            .expect("the lines of the preset are not replaced")
+    }
+
+    /// Set the combined bar to the sum of both bars,
+    /// it is indeterminate unless both totals are known.
+    fn update_combined(&mut self) {
+        let (first, second) = (self.first(), self.second());
+        let position = first.position().saturating_add(second.position());
+        let total = first
+            .total()
+            .zip(second.total())
+            .map(|(first, second)| first.saturating_add(second));
+
+        self.progress_at_mut(3)
+            .set_total(total)
+            .set_position(position);
+    }
+}
+
+impl core::fmt::Display for DualProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.group.fmt(f)
+    }
+}
+
+impl Frames for DualProgress {
+    fn advance(&mut self) {
+        self.update_combined();
+        self.group.advance();
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.update_combined();
+        self.group.advance_at(now);
+    }
+
+    fn render(&self, buf: &mut String) {
+        self.group.render(buf);
+    }
+
+    fn repaint(&self, buf: &mut String) {
+        self.group.repaint(buf);
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.group.is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.group.mark_clean();
+    }
+
+    fn reset(&mut self) {
+        self.group.reset();
+    }
+
+    fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.group.clear(f)
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.group.rewind(f)
+    }
+
+    fn lines(&self) -> usize {
+        self.group.lines()
+    }
+
+    fn total_lines(&self) -> usize {
+        self.group.total_lines()
+    }
+
+    fn displayed_lines(&self) -> usize {
+        self.group.displayed_lines()
+    }
+
+    fn lines_changed(&self) -> bool {
+        self.group.lines_changed()
+    }
+
+    fn state(&self) -> FrameState {
+        self.first().state().min(self.second().state())
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.progress_at(3).fraction()
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    io::{self, BufRead, Read, Write},
+    sync::Arc,
+};
+
+use super::ProgressCounter;
+
+/// Count the bytes read from any reader, see [`ProgressReader`].
+pub trait ReadProgressExt: Read + Sized {
+    /// Advance the counter by the amount of bytes read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::progress::{Progress, ReadProgressExt};
+    /// # use std::io::Read;
+    /// let mut progress = Progress::new().with_total(Some(5));
+    /// let mut reader = b"hello".as_slice().progress_to(progress.counter());
+    ///
+    /// let mut buf = String::new();
+    /// reader.read_to_string(&mut buf).unwrap();
+    ///
+    /// assert_eq!(progress.position(), 5);
+    /// ```
+    fn progress_to(self, counter: Arc<ProgressCounter>) -> ProgressReader<Self> {
+        ProgressReader {
+            inner: self,
+            counter,
+        }
+    }
+}
+
+impl<R: Read> ReadProgressExt for R {}
+
+/// Count the bytes written to any writer, see [`ProgressWriter`].
+///
+/// Types that are both readers and writers need to choose
+/// the trait explicitly if both traits are imported, e.g.
+/// `WriteProgressExt::progress_to(file, counter)`.
+pub trait WriteProgressExt: Write + Sized {
+    /// Advance the counter by the amount of bytes written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::progress::{Progress, WriteProgressExt};
+    /// # use std::io::Write;
+    /// let mut progress = Progress::new().with_total(Some(5));
+    /// let mut writer = Vec::new().progress_to(progress.counter());
+    ///
+    /// writer.write_all(b"hello").unwrap();
+    ///
+    /// assert_eq!(progress.position(), 5);
+    /// ```
+    fn progress_to(self, counter: Arc<ProgressCounter>) -> ProgressWriter<Self> {
+        ProgressWriter {
+            inner: self,
+            counter,
+        }
+    }
+}
+
+impl<W: Write> WriteProgressExt for W {}
+
+/// A reader that advances a counter by
+/// the amount of bytes read from it.
+#[derive(Debug)]
+pub struct ProgressReader<R> {
+    inner: R,
+    counter: Arc<ProgressCounter>,
+}
+
+impl<R> ProgressReader<R> {
+    /// The wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// The wrapped reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Return the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.counter.inc(n as u64);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for ProgressReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.counter.inc(amt as u64);
+    }
+}
+
+/// A writer that advances a counter by
+/// the amount of bytes written to it.
+#[derive(Debug)]
+pub struct ProgressWriter<W> {
+    inner: W,
+    counter: Arc<ProgressCounter>,
+}
+
+impl<W> ProgressWriter<W> {
+    /// The wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// The wrapped writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Return the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.counter.inc(n as u64);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
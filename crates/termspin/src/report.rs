@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use crate::{time::DurationFormat, util::display_width, FrameState, Frames, Line, SharedFrames};
+
+/// A summary of the labeled frames and their states,
+/// e.g. to print after the animated frames are cleared.
+///
+/// The report is displayed as a plain-text table
+/// with a row for each entry.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, FrameState, Group, Line, Report};
+/// let mut group = Group::new();
+/// group
+///     .push(Line::new(spinner::dots()).with_text("build").with_state(FrameState::Done))
+///     .push(Line::new(spinner::dots()).with_text("test").with_state(FrameState::Failed));
+///
+/// let report = group.report();
+/// assert_eq!(report.count(FrameState::Failed), 1);
+/// assert_eq!(report.to_string(), "build  done    0s\ntest   failed  0s");
+/// ```
+#[must_use]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Report {
+    entries: Vec<ReportEntry>,
+}
+
+/// A single entry of a [`Report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportEntry {
+    /// The label of the frames.
+    pub name: String,
+    /// The state of the frames.
+    pub state: FrameState,
+    /// The time the frames have been running for,
+    /// only known for lines.
+    pub duration: Option<Duration>,
+}
+
+impl Report {
+    /// Create a report of the given frames and
+    /// all their nested children that have a label.
+    pub fn new(frames: &dyn Frames) -> Self {
+        let mut report = Self::default();
+        report.collect(frames);
+        report
+    }
+
+    fn collect(&mut self, frames: &dyn Frames) {
+        if let Some(name) = frames.label() {
+            self.entries.push(ReportEntry {
+                name,
+                state: frames.state(),
+                duration: line_duration(frames),
+            });
+        }
+
+        frames.for_each_child(&mut |child| self.collect(child));
+    }
+
+    /// The entries in the order the frames are displayed.
+    #[must_use]
+    pub fn entries(&self) -> &[ReportEntry] {
+        &self.entries
+    }
+
+    /// The amount of entries in the given state.
+    #[must_use]
+    pub fn count(&self, state: FrameState) -> usize {
+        self.entries.iter().filter(|e| e.state == state).count()
+    }
+}
+
+/// The duration of the frames if they are a line or a shared line.
+pub(crate) fn line_duration(frames: &dyn Frames) -> Option<Duration> {
+    if let Ok(line) = frames.downcast_ref::<Line>() {
+        Some(line.duration())
+    } else if let Ok(shared) = frames.downcast_ref::<SharedFrames<Line>>() {
+        Some(shared.lock_inner().duration())
+    } else {
+        None
+    }
+}
+
+/// The name of the state in a report.
+pub(crate) fn state_name(state: FrameState) -> &'static str {
+    match state {
+        FrameState::Failed => "failed",
+        FrameState::Running => "running",
+        FrameState::Paused => "paused",
+        FrameState::Done => "done",
+    }
+}
+
+impl core::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name_width = self
+            .entries
+            .iter()
+            .map(|e| display_width(&e.name))
+            .max()
+            .unwrap_or(0);
+        let state_width = self
+            .entries
+            .iter()
+            .map(|e| state_name(e.state).len())
+            .max()
+            .unwrap_or(0);
+
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("\n")?;
+            }
+
+            let name_padding = name_width - display_width(&entry.name);
+            write!(f, "{}{:name_padding$}  ", entry.name, "")?;
+
+            let state = state_name(entry.state);
+            match entry.duration {
+                Some(duration) => write!(
+                    f,
+                    "{state:state_width$}  {}",
+                    DurationFormat::Compact.display(duration)
+                )?,
+                None => f.write_str(state)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::{spinner, FrameState, Frames, Line, Loop, SharedFrames};
+
+/// The interval the line is redrawn at.
+const INTERVAL: Duration = Duration::from_millis(100);
+
+/// Display a line with the given text on the standard error
+/// while the closure runs.
+///
+/// The line is finished as done or failed depending on the
+/// result of the closure and is left displayed, it is also
+/// finished as failed if the closure panics.
+///
+/// # Example
+///
+/// ```no_run
+/// # use termspin::with_spinner;
+/// let config = with_spinner("reading config", || std::fs::read_to_string("config.toml"))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[allow(clippy::missing_errors_doc)]
+pub fn with_spinner<T, E>(text: &str, f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    let spinning = Spinning::start(Line::new(spinner::dots()).with_text(text));
+    let res = f();
+    spinning.finish(res.is_ok());
+    res
+}
+
+/// A line drawn on the standard error until dropped,
+/// the line is left displayed afterwards.
+///
+/// Lines that are not finished when dropped, e.g. during
+/// a panic, are finished as failed.
+pub(crate) struct Spinning {
+    line: SharedFrames<Line>,
+    draw: Loop<SharedFrames<Line>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Spinning {
+    /// Start drawing the line on a separate thread.
+    pub(crate) fn start(line: Line) -> Self {
+        let line = line.shared();
+        let draw = Loop::new(INTERVAL, line.clone());
+        let thread = draw.spawn_thread(io::stderr());
+
+        Self { line, draw, thread }
+    }
+
+    /// Finish the line as done or failed and stop drawing it.
+    pub(crate) fn finish(self, ok: bool) {
+        self.line.lock().set_state(if ok {
+            FrameState::Done
+        } else {
+            FrameState::Failed
+        });
+    }
+}
+
+impl Drop for Spinning {
+    fn drop(&mut self) {
+        {
+            let mut line = self.line.lock();
+            if !line.is_finished() {
+                line.set_state(FrameState::Failed);
+            }
+        }
+
+        let Some(thread) = self.thread.take() else {
+            return;
+        };
+
+        self.draw.stop();
+        let _ = thread.join();
+
+        let line = self.line.lock();
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "{}", line.clear());
+        let _ = writeln!(stderr, "{}", *line);
+    }
+}
@@ -1,14 +1,152 @@
 //! Various spinner implementations and utilities for [`Frames`].
 
-use crate::Frames;
+use std::{
+    borrow::Cow,
+    fmt::Write,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    ansi::ResetStyle,
+    text::Style,
+    util::{display_width, DisplayFn, Token, Tokens},
+    Frames,
+};
+
+/// Implement the given [`Frames`] methods by calling them on
+/// a field, e.g. `frames`, or on the result of a method
+/// returning `&dyn Frames`, e.g. `current()`.
+macro_rules! forward_frames {
+    ($getter:ident(); $($method:ident),+ $(,)?) => {
+        forward_frames!(@each [.$getter()] $($method),+);
+    };
+    ($field:ident; $($method:ident),+ $(,)?) => {
+        forward_frames!(@each [.$field] $($method),+);
+    };
+    (@each $inner:tt $method:ident $(, $rest:ident)*) => {
+        forward_frames!(@method $inner $method);
+        $(forward_frames!(@method $inner $rest);)*
+    };
+    (@method [$($inner:tt)*] advance) => {
+        fn advance(&mut self) {
+            self$($inner)*.advance();
+        }
+    };
+    (@method [$($inner:tt)*] advance_at) => {
+        fn advance_at(&mut self, now: Instant) {
+            self$($inner)*.advance_at(now);
+        }
+    };
+    (@method [$($inner:tt)*] reset) => {
+        fn reset(&mut self) {
+            self$($inner)*.reset();
+        }
+    };
+    (@method [$($inner:tt)*] render) => {
+        fn render(&self, buf: &mut String) {
+            self$($inner)*.render(buf);
+        }
+    };
+    (@method [$($inner:tt)*] repaint) => {
+        fn repaint(&self, buf: &mut String) {
+            self$($inner)*.repaint(buf);
+        }
+    };
+    (@method [$($inner:tt)*] is_dirty) => {
+        fn is_dirty(&self) -> bool {
+            self$($inner)*.is_dirty()
+        }
+    };
+    (@method [$($inner:tt)*] mark_clean) => {
+        fn mark_clean(&mut self) {
+            self$($inner)*.mark_clean();
+        }
+    };
+    (@method [$($inner:tt)*] clear) => {
+        fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+            self$($inner)*.clear(f)
+        }
+    };
+    (@method [$($inner:tt)*] rewind) => {
+        fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+            self$($inner)*.rewind(f)
+        }
+    };
+    (@method [$($inner:tt)*] lines) => {
+        fn lines(&self) -> usize {
+            self$($inner)*.lines()
+        }
+    };
+    (@method [$($inner:tt)*] total_lines) => {
+        fn total_lines(&self) -> usize {
+            self$($inner)*.total_lines()
+        }
+    };
+    (@method [$($inner:tt)*] displayed_lines) => {
+        fn displayed_lines(&self) -> usize {
+            self$($inner)*.displayed_lines()
+        }
+    };
+    (@method [$($inner:tt)*] lines_changed) => {
+        fn lines_changed(&self) -> bool {
+            self$($inner)*.lines_changed()
+        }
+    };
+    (@method [$($inner:tt)*] frame_count) => {
+        fn frame_count(&self) -> Option<usize> {
+            self$($inner)*.frame_count()
+        }
+    };
+    (@method [$($inner:tt)*] state) => {
+        fn state(&self) -> crate::FrameState {
+            self$($inner)*.state()
+        }
+    };
+    (@method [$($inner:tt)*] is_finished) => {
+        fn is_finished(&self) -> bool {
+            self$($inner)*.is_finished()
+        }
+    };
+    (@method [$($inner:tt)*] progress) => {
+        fn progress(&self) -> Option<f64> {
+            self$($inner)*.progress()
+        }
+    };
+    (@method [$($inner:tt)*] print_len) => {
+        fn print_len(&self) -> Option<usize> {
+            self$($inner)*.print_len()
+        }
+    };
+    (@method [$($inner:tt)*] interval) => {
+        fn interval(&self) -> Option<Duration> {
+            self$($inner)*.interval()
+        }
+    };
+    (@method [$($inner:tt)*] label) => {
+        fn label(&self) -> Option<String> {
+            self$($inner)*.label()
+        }
+    };
+    (@method [$($inner:tt)*] for_each_child) => {
+        fn for_each_child(&self, f: &mut dyn FnMut(&dyn Frames)) {
+            self$($inner)*.for_each_child(f);
+        }
+    };
+}
 
 /// Create frames from an iterator.
 ///
+/// The iterator can be infinite, the frames only report
+/// a [`Frames::frame_count`] if it ends within the first
+/// few thousand frames.
+///
 /// # Example
 ///
 /// ```
-/// # use termspin::spinner::from_iter;
-/// from_iter([r"\", "|", "/"]);
+/// # use termspin::{spinner::from_iter, Frames};
+/// assert_eq!(from_iter([r"\", "|", "/"]).frame_count(), Some(3));
+/// assert_eq!(from_iter((0..).map(|i| i % 10)).frame_count(), None);
 /// ```
 pub fn from_iter<I, F>(iter: I) -> FromIter<I::IntoIter, F>
 where
@@ -19,6 +157,177 @@ where
     FromIter::new(iter.into_iter())
 }
 
+/// Create frames from an iterator that cannot be cloned,
+/// e.g. one that reads the frames from a file or a channel.
+///
+/// The frames are collected as they are first displayed
+/// and the stored frames are cycled after the iterator ends.
+/// Reading the next frame blocks the loop until it is available.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Frames};
+/// # use std::sync::mpsc;
+/// let (tx, rx) = mpsc::channel();
+/// for frame in ["a", "b"] {
+///     tx.send(frame).unwrap();
+/// }
+/// drop(tx);
+///
+/// let mut frames = spinner::from_iter_buffered(rx);
+/// assert_eq!(frames.frame_count(), None);
+///
+/// let mut played = Vec::new();
+/// for _ in 0..4 {
+///     played.push(frames.to_string());
+///     frames.advance();
+/// }
+///
+/// assert_eq!(played, ["a", "b", "a", "b"]);
+/// assert_eq!(frames.frame_count(), Some(2));
+/// ```
+pub fn from_iter_buffered<I, F>(iter: I) -> Buffered<I::IntoIter, F>
+where
+    I: IntoIterator<Item = F>,
+    F: core::fmt::Display,
+{
+    Buffered::new(iter.into_iter())
+}
+
+/// Check that every frame has the same width and
+/// contains no new lines or other control characters,
+/// which would break clearing the printed frames.
+///
+/// Escape sequences, e.g. for styling, are allowed.
+/// Frames without a known [`Frames::frame_count`] are checked
+/// for the first 100 frames. The frames are reset afterwards.
+///
+/// In debug builds, the first 100 frames created by [`from_iter`]
+/// are checked for new lines and other control characters,
+/// frames of different widths are allowed there.
+///
+/// # Errors
+///
+/// Returns the first invalid frame.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::spinner::{self, InvalidFrameKind};
+/// assert!(spinner::validate(&mut spinner::dots()).is_ok());
+///
+/// let err = spinner::validate(&mut spinner::from_array(["a", "bb"])).unwrap_err();
+/// assert_eq!(err.index(), 1);
+/// assert_eq!(err.kind(), InvalidFrameKind::Width { expected: 1, found: 2 });
+/// ```
+pub fn validate<F: Frames + ?Sized>(frames: &mut F) -> Result<(), InvalidFrame> {
+    frames.reset();
+
+    let count = frames.frame_count().unwrap_or(VALIDATED_FRAMES);
+    let res = validate_frames((0..count).map(|_| {
+        let frame = frames.to_string();
+        frames.advance();
+        frame
+    }));
+
+    frames.reset();
+    res
+}
+
+/// The amount of frames checked if the frame count is not known.
+const VALIDATED_FRAMES: usize = 100;
+
+/// The most frames counted for frames created from an iterator.
+const COUNTED_FRAMES: usize = 4096;
+
+fn validate_frames(frames: impl Iterator<Item = String>) -> Result<(), InvalidFrame> {
+    let mut expected = None;
+
+    for (index, frame) in frames.enumerate() {
+        let invalid = |kind| Err(InvalidFrame { index, kind });
+
+        if let Some(kind) = invalid_char(&frame) {
+            return invalid(kind);
+        }
+
+        let found = display_width(&frame);
+        match expected {
+            Some(expected) if expected != found => {
+                return invalid(InvalidFrameKind::Width { expected, found });
+            }
+            _ => expected = Some(found),
+        }
+    }
+
+    Ok(())
+}
+
+/// The reason the frame is invalid if it contains
+/// a new line or other control characters.
+fn invalid_char(frame: &str) -> Option<InvalidFrameKind> {
+    Tokens(frame).find_map(|token| match token {
+        Token::Control('\n') => Some(InvalidFrameKind::NewLine),
+        Token::Control(c) => Some(InvalidFrameKind::Control(c)),
+        Token::Text(_) | Token::Escape(_) => None,
+    })
+}
+
+/// The error returned by [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidFrame {
+    index: usize,
+    kind: InvalidFrameKind,
+}
+
+impl InvalidFrame {
+    /// The index of the invalid frame.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The reason the frame is invalid.
+    #[must_use]
+    pub fn kind(&self) -> InvalidFrameKind {
+        self.kind
+    }
+}
+
+impl core::fmt::Display for InvalidFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "frame {} ", self.index)?;
+
+        match self.kind {
+            InvalidFrameKind::NewLine => f.write_str("contains a new line"),
+            InvalidFrameKind::Control(c) => write!(f, "contains the control character {c:?}"),
+            InvalidFrameKind::Width { expected, found } => {
+                write!(f, "is {found} columns wide instead of {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidFrame {}
+
+/// The reason a frame is invalid, see [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidFrameKind {
+    /// The frame contains a new line.
+    NewLine,
+    /// The frame contains a control character
+    /// other than a new line.
+    Control(char),
+    /// The width of the frame differs from the
+    /// width of the first frame.
+    Width {
+        /// The width of the first frame.
+        expected: usize,
+        /// The width of the invalid frame.
+        found: usize,
+    },
+}
+
 /// Create empty spinner that does not display anything.
 #[must_use]
 pub fn empty() -> Empty {
@@ -31,6 +340,22 @@ pub const fn dots() -> FromArray<10, &'static str> {
     FromArray::new(["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
 }
 
+/// Create a spinner from a rotating globe.
+///
+/// The frames are two columns wide.
+#[must_use]
+pub const fn earth() -> FromArray<3, &'static str> {
+    FromArray::new(["🌍", "🌎", "🌏"])
+}
+
+/// Create a spinner from the phases of the moon.
+///
+/// The frames are two columns wide.
+#[must_use]
+pub const fn moon() -> FromArray<8, &'static str> {
+    FromArray::new(["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"])
+}
+
 /// Create a spinner from an array.
 pub const fn from_array<const N: usize, F>(array: [F; N]) -> FromArray<N, F>
 where
@@ -39,6 +364,331 @@ where
     FromArray::new(array)
 }
 
+/// Create a spinner where each frame is displayed
+/// for its own duration.
+///
+/// Loops only honor the durations if they negotiate their
+/// interval with the frames, see [`Loop::negotiate_interval`].
+///
+/// [`Loop::negotiate_interval`]: crate::Loop::negotiate_interval
+///
+/// # Panics
+///
+/// Panics if there are no frames.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Line, Loop};
+/// # use std::time::Duration;
+/// let spinner = spinner::from_timed([
+///     ("⠋", Duration::from_millis(80)),
+///     ("✨", Duration::from_millis(400)),
+/// ]);
+///
+/// let l = Loop::new(Duration::from_millis(100), Line::new(spinner));
+/// l.negotiate_interval(true);
+/// ```
+pub fn from_timed<I, F>(frames: I) -> Timed<F>
+where
+    I: IntoIterator<Item = (F, Duration)>,
+    F: core::fmt::Display,
+{
+    let frames: Vec<_> = frames.into_iter().collect();
+    assert!(!frames.is_empty(), "the frames cannot be empty.");
+
+    Timed {
+        idx: 0,
+        shown: 0,
+        frames,
+    }
+}
+
+/// Render the given amount of frames once in advance
+/// and cycle through the rendered output.
+///
+/// This avoids formatting the frames on every tick for
+/// frames that do not change other than by advancing,
+/// e.g. groups of lines with static text.
+///
+/// # Panics
+///
+/// Panics if `count` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Line};
+/// spinner::precompute(Line::new(spinner::dots()).with_text("loading"), 10);
+/// ```
+pub fn precompute(frames: impl Frames, count: usize) -> Precomputed {
+    Precomputed::new(frames, count)
+}
+
+/// Create an empty sequence of frames that are played
+/// after each other.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::spinner::{self, Repeat};
+/// spinner::seq()
+///     .play(spinner::from_array(["·", "•", "●"]), 1)
+///     .play(spinner::dots(), Repeat::Forever);
+/// ```
+pub fn seq() -> Sequence {
+    Sequence::new()
+}
+
+/// Create an empty set of named phases that each
+/// display different frames.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Line};
+/// # use termspin::progress::Progress;
+/// let mut line = Line::new(
+///     spinner::phases()
+///         .with_phase("connect", spinner::from_array(["·", "•", "●"]))
+///         .with_phase("transfer", Progress::new())
+///         .with_phase("verify", spinner::dots()),
+/// );
+///
+/// line.spinner_as_mut::<spinner::Phases>()
+///     .unwrap()
+///     .set_phase("transfer");
+/// ```
+pub fn phases() -> Phases {
+    Phases::new()
+}
+
+/// Declare the preferred interval between advances of the
+/// given frames, see [`Frames::interval`].
+///
+/// Groups advance paced children on their own cadence, e.g. a
+/// spinner paced at 80ms and a clock that changes every second
+/// can share a group without advancing the clock on every tick.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, time, Group, Loop};
+/// # use std::time::Duration;
+/// let mut group = Group::new();
+/// group
+///     .push(spinner::paced(Duration::from_millis(80), spinner::dots()))
+///     .push(time::clock());
+///
+/// let l = Loop::new(Duration::from_millis(100), group);
+/// l.negotiate_interval(true);
+/// ```
+pub fn paced<F: Frames>(interval: Duration, frames: F) -> Paced<F> {
+    Paced { interval, frames }
+}
+
+/// Advance the given frames only on every `n`th advance,
+/// zero is treated as one.
+///
+/// This slows down some spinners of a group driven by a
+/// single loop without the need for separate loops.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Frames};
+/// let mut frames = spinner::throttle(2, spinner::from_array(["a", "b"]));
+///
+/// let mut played = String::new();
+/// for _ in 0..4 {
+///     played += &frames.to_string();
+///     frames.advance();
+/// }
+///
+/// assert_eq!(played, "aabb");
+/// ```
+pub fn throttle<F: Frames>(n: usize, frames: F) -> Throttle<F> {
+    Throttle {
+        every: n.max(1),
+        skipped: 0,
+        frames,
+    }
+}
+
+/// Display the given frames in a style.
+///
+/// The style can also be chosen from the current
+/// frames with [`Styled::with_style_fn`].
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Line};
+/// # use termspin::text::{Color, Style};
+/// let spinner = spinner::styled(Style::new().with_fg(Color::Cyan), spinner::dots());
+/// let line = Line::new(spinner).with_text("loading");
+/// ```
+pub fn styled<F: Frames>(style: Style, frames: F) -> Styled<F> {
+    Styled {
+        style: StyleSource::Fixed(style),
+        frames,
+        clean: None,
+    }
+}
+
+/// Play all frames once and stay on the last one,
+/// the frames are done afterwards.
+///
+/// Frames without a known [`Frames::frame_count`]
+/// are advanced until they are [finished](Frames::is_finished)
+/// and report their own state.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, FrameState, Frames, Line};
+/// let mut frames = spinner::once(spinner::from_array(["·  ", "·· ", "···"]));
+///
+/// for _ in 0..5 {
+///     frames.advance();
+/// }
+///
+/// assert_eq!(frames.to_string(), "···");
+/// assert!(frames.is_finished());
+///
+/// // Lines do not know their frame count.
+/// let mut line = spinner::once(Line::new(spinner::dots()));
+/// line.advance();
+/// assert!(!line.is_finished());
+///
+/// line.inner_mut().set_state(FrameState::Failed);
+/// assert_eq!(line.state(), FrameState::Failed);
+/// ```
+pub fn once<F: Frames>(frames: F) -> OneShot<F> {
+    OneShot {
+        frames,
+        advanced: 0,
+        clean: false,
+    }
+}
+
+/// Transform the displayed text of the given frames,
+/// e.g. to add brackets or padding.
+///
+/// The function writes the output with the current
+/// frame, so that nothing is allocated in between.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::spinner;
+/// let frames = spinner::map(spinner::dots(), |frame, f| write!(f, "[{frame}]"));
+/// assert_eq!(frames.to_string(), "[⠋]");
+/// ```
+pub fn map<F, M>(frames: F, f: M) -> Map<F, M>
+where
+    F: Frames,
+    M: Fn(&dyn core::fmt::Display, &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+        + Send
+        + Sync
+        + 'static,
+{
+    Map { frames, f }
+}
+
+/// Create frames from a function that writes the frame
+/// for the given tick, which counts the advances
+/// since the start.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Frames};
+/// let mut frames = spinner::from_fn(|tick, f| write!(f, "{}", ".".repeat(tick % 3 + 1)));
+///
+/// frames.advance();
+/// assert_eq!(frames.to_string(), "..");
+/// ```
+pub fn from_fn<M>(f: M) -> FromFn<M>
+where
+    M: Fn(usize, &mut core::fmt::Formatter<'_>) -> core::fmt::Result + Send + Sync + 'static,
+{
+    FromFn { tick: 0, f }
+}
+
+/// Play all frames of `first` once, then loop `then`.
+///
+/// This is a typed shorthand for a [`seq`] of two phases.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Frames};
+/// let mut spinner = spinner::chain(
+///     spinner::from_array(["·", "•"]),
+///     spinner::from_array(["a", "b"]),
+/// );
+///
+/// let mut played = Vec::new();
+/// for _ in 0..5 {
+///     played.push(spinner.to_string());
+///     spinner.advance();
+/// }
+///
+/// assert_eq!(played, ["·", "•", "a", "b", "a"]);
+/// ```
+pub fn chain<A: Frames, B: Frames>(first: A, then: B) -> Chain<A, B> {
+    Chain {
+        first,
+        then,
+        advanced: 0,
+        playing_first: true,
+        clean: None,
+    }
+}
+
+/// Play the frames of the given spinner in a random order,
+/// shuffled again at the start of every cycle,
+/// e.g. for glitch-like animations.
+///
+/// Every frame is displayed once per cycle, the order
+/// can be made deterministic with [`Shuffle::with_seed`].
+///
+/// # Panics
+///
+/// Panics if the frames do not have a [`Frames::frame_count`].
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Frames};
+/// let mut frames = spinner::shuffle(spinner::from_array(["a", "b", "c"])).with_seed(7);
+///
+/// let mut cycle = Vec::new();
+/// for _ in 0..3 {
+///     cycle.push(frames.to_string());
+///     frames.advance();
+/// }
+/// cycle.sort();
+///
+/// assert_eq!(cycle, ["a", "b", "c"]);
+/// ```
+pub fn shuffle<F: Frames>(frames: F) -> Shuffle<F> {
+    let count = frames
+        .frame_count()
+        .expect("the frames must have a frame count.");
+
+    let mut shuffle = Shuffle {
+        frames,
+        order: (0..count).collect(),
+        pos: 0,
+        at: 0,
+        rng: random_seed(),
+    };
+    shuffle.restart();
+    shuffle
+}
+
 /// Frames returned by [`from_iter`].
 #[derive(Debug, Clone, Copy)]
 pub struct FromIter<I, F>
@@ -49,6 +699,8 @@ where
     start: I,
     current: I,
     frame: Option<F>,
+    offset: usize,
+    frame_count: Option<usize>,
 }
 
 impl<I, F> FromIter<I, F>
@@ -57,13 +709,92 @@ where
     F: core::fmt::Display,
 {
     /// Create frames from an iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if any of the first frames contain
+    /// new lines or other control characters, see [`validate`].
     pub fn new(iter: I) -> Self {
+        #[cfg(debug_assertions)]
+        for (index, frame) in iter.clone().take(VALIDATED_FRAMES).enumerate() {
+            if let Some(kind) = invalid_char(&frame.to_string()) {
+                panic!("invalid frames: {}", InvalidFrame { index, kind });
+            }
+        }
+
         let frame = iter.clone().next();
+        // Infinite iterators are only counted up to the limit.
+        let frame_count = Some(iter.clone().take(COUNTED_FRAMES + 1).count())
+            .filter(|&count| count <= COUNTED_FRAMES);
 
         Self {
             start: iter.clone(),
             current: iter,
             frame,
+            offset: 0,
+            frame_count,
+        }
+    }
+
+    /// Start at the frame with the given index,
+    /// wrapping around if it is out of bounds.
+    ///
+    /// Resetting the frames returns to this frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::spinner;
+    /// let frames = spinner::from_iter(["a", "b", "c"]).with_offset(4);
+    /// assert_eq!(frames.to_string(), "b");
+    /// ```
+    #[must_use]
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self.rewind_to_offset();
+        self
+    }
+
+    /// Play the frames in the opposite direction,
+    /// starting from the last one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Frames};
+    /// let mut frames = spinner::from_iter(["a", "b", "c"]).reversed();
+    /// assert_eq!(frames.to_string(), "c");
+    ///
+    /// frames.advance();
+    /// assert_eq!(frames.to_string(), "b");
+    /// ```
+    #[must_use]
+    pub fn reversed(self) -> FromIter<std::iter::Rev<I>, F>
+    where
+        I: DoubleEndedIterator,
+    {
+        FromIter::new(self.start.rev()).with_offset(self.offset)
+    }
+
+    fn rewind_to_offset(&mut self) {
+        self.current = self.start.clone();
+        self.frame = self.current.next();
+
+        let mut remaining = self.offset;
+        let mut idx = 0;
+        while remaining > 0 && self.frame.is_some() {
+            remaining -= 1;
+            if let Some(frame) = self.current.next() {
+                self.frame = Some(frame);
+                idx += 1;
+            } else {
+                // The amount of frames is known once the iterator
+                // ends, so the remaining steps can wrap around.
+                remaining %= idx + 1;
+                self.current = self.start.clone();
+                self.frame = self.current.next();
+                idx = 0;
+            }
         }
     }
 }
@@ -96,25 +827,115 @@ where
     }
 
     fn reset(&mut self) {
-        self.frame = self.start.clone().next();
-        self.current = self.start.clone();
+        self.rewind_to_offset();
     }
-}
-
-/// Empty frames that do not display anything.
-pub struct Empty;
 
-impl core::fmt::Display for Empty {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Ok(())
+    fn frame_count(&self) -> Option<usize> {
+        self.frame_count
     }
 }
 
-impl Frames for Empty {
-    fn advance(&mut self) {}
-    fn print_len(&self) -> Option<usize> {
-        Some(0)
-    }
+/// Frames returned by [`from_iter_buffered`].
+#[derive(Debug)]
+pub struct Buffered<I, F> {
+    /// The iterator until it ends, in a mutex only
+    /// so that the frames can be shared between threads.
+    iter: Mutex<Option<I>>,
+    frames: Vec<F>,
+    idx: usize,
+}
+
+impl<I, F> Buffered<I, F>
+where
+    I: Iterator<Item = F>,
+    F: core::fmt::Display,
+{
+    /// Create frames from an iterator, the first
+    /// frame is read immediately.
+    pub fn new(iter: I) -> Self {
+        let mut frames = Self {
+            iter: Mutex::new(Some(iter)),
+            frames: Vec::new(),
+            idx: 0,
+        };
+        frames.read_next();
+        frames
+    }
+
+    /// The frames read so far.
+    pub fn buffered(&self) -> &[F] {
+        &self.frames
+    }
+
+    /// Read the next frame from the iterator, and return whether
+    /// there was one.
+    fn read_next(&mut self) -> bool {
+        let iter = self.iter.get_mut().unwrap();
+        let Some(frame) = iter.as_mut().and_then(Iterator::next) else {
+            *iter = None;
+            return false;
+        };
+
+        self.frames.push(frame);
+        true
+    }
+}
+
+impl<I, F> core::fmt::Display for Buffered<I, F>
+where
+    F: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(frame) = self.frames.get(self.idx) {
+            frame.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<I, F> Frames for Buffered<I, F>
+where
+    I: Iterator<Item = F> + Send + 'static,
+    F: core::fmt::Display + Send + Sync + 'static,
+{
+    fn advance(&mut self) {
+        if self.idx + 1 < self.frames.len() || self.read_next() {
+            self.idx += 1;
+        } else {
+            self.idx = 0;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.idx = 0;
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        let ended = self.iter.lock().unwrap().is_none();
+        ended.then_some(self.frames.len())
+    }
+}
+
+/// Empty frames that do not display anything.
+pub struct Empty;
+
+impl core::fmt::Display for Empty {
+    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl Frames for Empty {
+    fn advance(&mut self) {}
+    fn frame_count(&self) -> Option<usize> {
+        Some(1)
+    }
+    fn print_len(&self) -> Option<usize> {
+        Some(0)
+    }
+    fn is_dirty(&self) -> bool {
+        false
+    }
 }
 
 /// Frames returned by [`from_array`].
@@ -124,6 +945,10 @@ where
     F: core::fmt::Display,
 {
     idx: usize,
+    offset: usize,
+    bounce: bool,
+    reversed: bool,
+    backward: bool,
     array: [F; N],
 }
 
@@ -137,7 +962,84 @@ where
     #[must_use]
     pub const fn new(array: [F; N]) -> Self {
         assert!(N != 0, "the array cannot be empty.");
-        Self { idx: 0, array }
+        Self {
+            idx: 0,
+            offset: 0,
+            bounce: false,
+            reversed: false,
+            backward: false,
+            array,
+        }
+    }
+
+    /// Play the frames in the opposite direction.
+    ///
+    /// The first frame is still the one at the offset,
+    /// see [`FromArray::with_offset`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Frames};
+    /// let mut frames = spinner::from_array(["a", "b", "c"]).reversed();
+    ///
+    /// let mut played = String::new();
+    /// for _ in 0..4 {
+    ///     played += &frames.to_string();
+    ///     frames.advance();
+    /// }
+    ///
+    /// assert_eq!(played, "acba");
+    /// ```
+    #[must_use]
+    pub const fn reversed(mut self) -> Self {
+        self.reversed = !self.reversed;
+        self.backward = self.reversed;
+        self
+    }
+
+    /// Play the frames backwards after the last one
+    /// instead of starting over.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, Frames};
+    /// let mut frames = spinner::from_array(["a", "b", "c"]).bounce();
+    ///
+    /// let mut played = String::new();
+    /// for _ in 0..7 {
+    ///     played += &frames.to_string();
+    ///     frames.advance();
+    /// }
+    ///
+    /// assert_eq!(played, "abcbabc");
+    /// ```
+    #[must_use]
+    pub const fn bounce(mut self) -> Self {
+        self.bounce = true;
+        self
+    }
+
+    /// Start at the frame with the given index,
+    /// wrapping around if it is out of bounds.
+    ///
+    /// Resetting the frames returns to this frame,
+    /// which keeps spinners created with the same
+    /// offset in sync.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::spinner;
+    /// let frames = spinner::from_array(["a", "b", "c"]).with_offset(2);
+    /// assert_eq!(frames.to_string(), "c");
+    /// ```
+    #[must_use]
+    pub const fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset % N;
+        self.idx = self.offset;
+        self
     }
 }
 
@@ -155,14 +1057,1259 @@ where
     F: core::fmt::Display + Send + Sync + 'static,
 {
     fn advance(&mut self) {
-        if self.idx == self.array.len() - 1 {
+        if self.bounce {
+            self.idx = bounce_step(self.idx, N, &mut self.backward);
+        } else if self.reversed {
+            self.idx = self.idx.checked_sub(1).unwrap_or(N - 1);
+        } else if self.idx == self.array.len() - 1 {
             self.idx = 0;
         } else {
             self.idx += 1;
         }
     }
 
+    fn reset(&mut self) {
+        self.idx = self.offset;
+        self.backward = self.reversed;
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        Some(bounce_len(N, self.bounce))
+    }
+}
+
+/// Frames returned by [`from_timed`].
+#[derive(Debug, Clone)]
+pub struct Timed<F> {
+    idx: usize,
+    /// The frame displayed before the last advance, which
+    /// stays on the screen until the next tick.
+    shown: usize,
+    frames: Vec<(F, Duration)>,
+}
+
+impl<F: core::fmt::Display> core::fmt::Display for Timed<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.frames[self.idx].0.fmt(f)
+    }
+}
+
+impl<F> Frames for Timed<F>
+where
+    F: core::fmt::Display + Send + Sync + 'static,
+{
+    fn advance(&mut self) {
+        self.shown = self.idx;
+        self.idx = (self.idx + 1) % self.frames.len();
+    }
+
+    fn reset(&mut self) {
+        self.idx = 0;
+        self.shown = 0;
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        Some(self.frames.len())
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        Some(self.frames[self.shown].1)
+    }
+}
+
+/// Frames returned by [`precompute`].
+#[derive(Debug, Clone)]
+pub struct Precomputed {
+    idx: usize,
+    offset: usize,
+    bounce: bool,
+    backward: bool,
+    frames: Vec<PrecomputedFrame>,
+}
+
+#[derive(Debug, Clone)]
+struct PrecomputedFrame {
+    display: String,
+    clear: String,
+    rewind: String,
+    lines: usize,
+    total_lines: usize,
+    print_len: Option<usize>,
+}
+
+impl Precomputed {
+    /// Render the given amount of frames in advance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is zero.
+    pub fn new(mut frames: impl Frames, count: usize) -> Self {
+        assert!(count != 0, "the frame count cannot be zero.");
+
+        let frames = (0..count)
+            .map(|_| {
+                let display = frames.to_string();
+                frames.mark_clean();
+
+                let frame = PrecomputedFrame {
+                    display,
+                    clear: DisplayFn::new(|f| frames.clear(f)).to_string(),
+                    rewind: DisplayFn::new(|f| frames.rewind(f)).to_string(),
+                    lines: frames.lines(),
+                    total_lines: frames.total_lines(),
+                    print_len: frames.print_len(),
+                };
+                frames.advance();
+                frame
+            })
+            .collect();
+
+        Self {
+            idx: 0,
+            offset: 0,
+            bounce: false,
+            backward: false,
+            frames,
+        }
+    }
+
+    /// Play the frames backwards after the last one
+    /// instead of starting over, see [`FromArray::bounce`].
+    #[must_use]
+    pub fn bounce(mut self) -> Self {
+        self.bounce = true;
+        self
+    }
+
+    /// Start at the frame with the given index,
+    /// wrapping around if it is out of bounds.
+    ///
+    /// Resetting the frames returns to this frame.
+    #[must_use]
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset % self.frames.len();
+        self.idx = self.offset;
+        self
+    }
+
+    fn current(&self) -> &PrecomputedFrame {
+        &self.frames[self.idx]
+    }
+}
+
+impl core::fmt::Display for Precomputed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.current().display)
+    }
+}
+
+impl Frames for Precomputed {
+    fn advance(&mut self) {
+        if self.bounce {
+            self.idx = bounce_step(self.idx, self.frames.len(), &mut self.backward);
+        } else {
+            self.idx = (self.idx + 1) % self.frames.len();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.idx = self.offset;
+        self.backward = false;
+    }
+
+    fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.current().clear)
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.current().rewind)
+    }
+
+    fn lines(&self) -> usize {
+        self.current().lines
+    }
+
+    fn total_lines(&self) -> usize {
+        self.current().total_lines
+    }
+
+    fn print_len(&self) -> Option<usize> {
+        self.current().print_len
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        Some(bounce_len(self.frames.len(), self.bounce))
+    }
+}
+
+/// The index of the frame after the given one when
+/// playing `len` frames forward and backward.
+fn bounce_step(idx: usize, len: usize, backward: &mut bool) -> usize {
+    if len < 2 {
+        return 0;
+    }
+
+    if (*backward && idx == 0) || (!*backward && idx == len - 1) {
+        *backward = !*backward;
+    }
+
+    if *backward {
+        idx - 1
+    } else {
+        idx + 1
+    }
+}
+
+/// The amount of frames in a single cycle.
+fn bounce_len(len: usize, bounce: bool) -> usize {
+    if bounce && len > 1 {
+        len * 2 - 2
+    } else {
+        len
+    }
+}
+
+/// The amount of times frames are played in a [`Sequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Repeat {
+    /// Play all frames the given amount of times.
+    Times(usize),
+    /// Play the frames until the sequence is
+    /// moved to the next phase manually.
+    Forever,
+}
+
+impl From<usize> for Repeat {
+    fn from(times: usize) -> Self {
+        Repeat::Times(times)
+    }
+}
+
+/// Frames returned by [`seq`].
+///
+/// Each phase is played the given amount of times before
+/// moving on to the next one, the last frame is kept
+/// once all phases are played.
+///
+/// Frames that do not report a [`Frames::frame_count`]
+/// are treated as a single frame.
+#[must_use]
+#[derive(Default)]
+pub struct Sequence {
+    phases: Vec<(Box<dyn Frames>, Repeat)>,
+    idx: usize,
+    advanced: usize,
+    /// The phase when the frames were marked clean.
+    clean: Option<usize>,
+}
+
+impl Sequence {
+    /// Create an empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Play the frames after all previous phases.
+    pub fn play(mut self, frames: impl Frames, repeat: impl Into<Repeat>) -> Self {
+        let repeat = repeat.into();
+
+        if repeat != Repeat::Times(0) {
+            self.phases.push((Box::new(frames), repeat));
+        }
+
+        self
+    }
+
+    /// The index of the currently played phase.
+    #[must_use]
+    pub fn phase(&self) -> usize {
+        self.idx
+    }
+
+    /// Move to the next phase immediately,
+    /// no-op if the current phase is the last one.
+    pub fn next_phase(&mut self) -> &mut Self {
+        if self.idx + 1 < self.phases.len() {
+            self.idx += 1;
+            self.advanced = 0;
+            self.phases[self.idx].0.reset();
+        }
+        self
+    }
+
+    /// The frames of the current phase, empty ones if there is none.
+    fn current(&self) -> &dyn Frames {
+        self.phases
+            .get(self.idx)
+            .map_or(&Empty, |(frames, _)| &**frames)
+    }
+
+    /// Advance the current phase, at the given time if any.
+    fn step(&mut self, now: Option<Instant>) {
+        let Some((frames, repeat)) = self.phases.get_mut(self.idx) else {
+            return;
+        };
+
+        if let Repeat::Times(times) = *repeat {
+            let total = times * frames.frame_count().unwrap_or(1);
+
+            if self.advanced + 1 >= total {
+                self.next_phase();
+                return;
+            }
+        }
+
+        match now {
+            Some(now) => frames.advance_at(now),
+            None => frames.advance(),
+        }
+        self.advanced += 1;
+    }
+}
+
+impl core::fmt::Display for Sequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.current().fmt(f)
+    }
+}
+
+impl Frames for Sequence {
+    fn advance(&mut self) {
+        self.step(None);
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.step(Some(now));
+    }
+
     fn reset(&mut self) {
         self.idx = 0;
+        self.advanced = 0;
+
+        for (frames, _) in &mut self.phases {
+            frames.reset();
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some(self.idx) || self.current().is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = Some(self.idx);
+        if let Some((frames, _)) = self.phases.get_mut(self.idx) {
+            frames.mark_clean();
+        }
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        self.phases
+            .iter()
+            .map(|(frames, repeat)| match repeat {
+                Repeat::Times(times) => Some(times * frames.frame_count().unwrap_or(1)),
+                Repeat::Forever => None,
+            })
+            .sum()
+    }
+
+    forward_frames!(
+        current();
+        render,
+        repaint,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        state,
+        is_finished,
+        progress,
+        print_len,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`phases`].
+///
+/// Only the frames of the current phase are displayed
+/// and advanced, the first added phase is the current one
+/// initially.
+#[must_use]
+#[derive(Default)]
+#[allow(clippy::struct_field_names)]
+pub struct Phases {
+    phases: Vec<(Cow<'static, str>, Box<dyn Frames>)>,
+    idx: usize,
+    /// The phase when the frames were marked clean.
+    clean: Option<usize>,
+}
+
+impl Phases {
+    /// Create an empty set of phases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a phase with the given name,
+    /// replacing any existing phase with the same name.
+    pub fn with_phase(mut self, name: &str, frames: impl Frames) -> Self {
+        self.add_phase(name, frames);
+        self
+    }
+
+    /// Add a phase with the given name,
+    /// replacing any existing phase with the same name.
+    pub fn add_phase(&mut self, name: &str, frames: impl Frames) -> &mut Self {
+        let frames: Box<dyn Frames> = Box::new(frames);
+
+        match self.phases.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = frames,
+            None => self.phases.push((name.to_string().into(), frames)),
+        }
+
+        self
+    }
+
+    /// Switch to the phase with the given name,
+    /// its frames start from the first one.
+    ///
+    /// No-op if the phase is already the current one
+    /// or it does not exist.
+    pub fn set_phase(&mut self, name: &str) -> &mut Self {
+        if let Some(idx) = self.phases.iter().position(|(n, _)| n == name) {
+            if idx != self.idx {
+                self.idx = idx;
+                self.phases[idx].1.reset();
+            }
+        }
+        self
+    }
+
+    /// The name of the current phase.
+    #[must_use]
+    pub fn phase(&self) -> Option<&str> {
+        self.phases.get(self.idx).map(|(name, _)| &**name)
+    }
+
+    /// The frames of the current phase, empty ones if there is none.
+    fn current(&self) -> &dyn Frames {
+        self.phases
+            .get(self.idx)
+            .map_or(&Empty, |(_, frames)| &**frames)
+    }
+}
+
+impl core::fmt::Display for Phases {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.current().fmt(f)
+    }
+}
+
+impl Frames for Phases {
+    fn advance(&mut self) {
+        if let Some((_, frames)) = self.phases.get_mut(self.idx) {
+            frames.advance();
+        }
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        if let Some((_, frames)) = self.phases.get_mut(self.idx) {
+            frames.advance_at(now);
+        }
+    }
+
+    fn reset(&mut self) {
+        if let Some((_, frames)) = self.phases.get_mut(self.idx) {
+            frames.reset();
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some(self.idx) || self.current().is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = Some(self.idx);
+        if let Some((_, frames)) = self.phases.get_mut(self.idx) {
+            frames.mark_clean();
+        }
+    }
+
+    forward_frames!(
+        current();
+        render,
+        repaint,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        frame_count,
+        state,
+        is_finished,
+        progress,
+        print_len,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`paced`].
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Paced<F> {
+    interval: Duration,
+    frames: F,
+}
+
+impl<F: Frames> Paced<F> {
+    /// The paced frames.
+    pub fn inner(&self) -> &F {
+        &self.frames
+    }
+
+    /// The paced frames.
+    pub fn inner_mut(&mut self) -> &mut F {
+        &mut self.frames
+    }
+}
+
+impl<F: Frames> core::fmt::Display for Paced<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.frames.fmt(f)
+    }
+}
+
+impl<F: Frames> Frames for Paced<F> {
+    forward_frames!(
+        frames;
+        advance,
+        advance_at,
+        reset,
+        render,
+        repaint,
+        is_dirty,
+        mark_clean,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        frame_count,
+        state,
+        is_finished,
+        progress,
+        print_len,
+        label,
+        for_each_child,
+    );
+
+    fn interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+}
+
+/// Frames returned by [`throttle`].
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Throttle<F> {
+    every: usize,
+    /// The amount of advances since the frames were last advanced.
+    skipped: usize,
+    frames: F,
+}
+
+impl<F: Frames> Throttle<F> {
+    /// The throttled frames.
+    pub fn inner(&self) -> &F {
+        &self.frames
+    }
+
+    /// The throttled frames.
+    pub fn inner_mut(&mut self) -> &mut F {
+        &mut self.frames
+    }
+
+    /// Whether the frames are advanced by the next advance.
+    fn is_due(&mut self) -> bool {
+        self.skipped += 1;
+        if self.skipped < self.every {
+            return false;
+        }
+        self.skipped = 0;
+        true
+    }
+}
+
+impl<F: Frames> core::fmt::Display for Throttle<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.frames.fmt(f)
+    }
+}
+
+impl<F: Frames> Frames for Throttle<F> {
+    fn advance(&mut self) {
+        if self.is_due() {
+            self.frames.advance();
+        }
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        if self.is_due() {
+            self.frames.advance_at(now);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.skipped = 0;
+        self.frames.reset();
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        self.frames.frame_count().map(|count| count * self.every)
+    }
+
+    forward_frames!(
+        frames;
+        render,
+        repaint,
+        is_dirty,
+        mark_clean,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        state,
+        is_finished,
+        progress,
+        print_len,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`styled`].
+#[must_use]
+pub struct Styled<F> {
+    style: StyleSource<F>,
+    frames: F,
+    /// The style when the frames were marked clean.
+    clean: Option<Style>,
+}
+
+/// The way the style of [`Styled`] is chosen.
+enum StyleSource<F> {
+    Fixed(Style),
+    Fn(Box<dyn Fn(&F) -> Style + Send + Sync>),
+}
+
+impl<F: Frames> Styled<F> {
+    /// Set the style of the frames.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = StyleSource::Fixed(style);
+        self
+    }
+
+    /// Set the style of the frames.
+    pub fn set_style(&mut self, style: Style) -> &mut Self {
+        self.style = StyleSource::Fixed(style);
+        self
+    }
+
+    /// Choose the style each time the frames are displayed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, FrameState, Frames, Line};
+    /// # use termspin::text::{Color, Style};
+    /// let mut line = spinner::styled(Style::new(), Line::new(spinner::dots()))
+    ///     .with_style_fn(|line| match line.state() {
+    ///         FrameState::Failed => Style::new().with_fg(Color::Red),
+    ///         _ => Style::new(),
+    ///     });
+    ///
+    /// line.inner_mut().set_state(FrameState::Failed);
+    /// assert!(line.to_string().starts_with("\x1B[31m"));
+    /// ```
+    pub fn with_style_fn(mut self, f: impl Fn(&F) -> Style + Send + Sync + 'static) -> Self {
+        self.style = StyleSource::Fn(Box::new(f));
+        self
+    }
+
+    /// Choose the style each time the frames are displayed.
+    pub fn set_style_fn(&mut self, f: impl Fn(&F) -> Style + Send + Sync + 'static) -> &mut Self {
+        self.style = StyleSource::Fn(Box::new(f));
+        self
+    }
+
+    /// The current style of the frames.
+    pub fn style(&self) -> Style {
+        match &self.style {
+            StyleSource::Fixed(style) => *style,
+            StyleSource::Fn(f) => f(&self.frames),
+        }
+    }
+
+    /// The styled frames.
+    pub fn inner(&self) -> &F {
+        &self.frames
+    }
+
+    /// The styled frames.
+    pub fn inner_mut(&mut self) -> &mut F {
+        &mut self.frames
+    }
+}
+
+impl<F: core::fmt::Debug> core::fmt::Debug for Styled<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Styled");
+        match &self.style {
+            StyleSource::Fixed(style) => debug.field("style", style),
+            StyleSource::Fn(_) => debug.field("style", &"fn"),
+        };
+        debug.field("frames", &self.frames).finish_non_exhaustive()
+    }
+}
+
+impl<F: Frames> core::fmt::Display for Styled<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let style = self.style();
+        style.fmt(f)?;
+        self.frames.fmt(f)?;
+        if !style.is_plain() {
+            ResetStyle.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: Frames> Frames for Styled<F> {
+    fn render(&self, buf: &mut String) {
+        let style = self.style();
+        let _ = write!(buf, "{style}");
+        self.frames.render(buf);
+        if !style.is_plain() {
+            let _ = write!(buf, "{ResetStyle}");
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some(self.style()) || self.frames.is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = Some(self.style());
+        self.frames.mark_clean();
+    }
+
+    forward_frames!(
+        frames;
+        advance,
+        advance_at,
+        reset,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        frame_count,
+        state,
+        is_finished,
+        progress,
+        print_len,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`chain`].
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Chain<A, B> {
+    first: A,
+    then: B,
+    advanced: usize,
+    playing_first: bool,
+    /// Whether the first frames were played when marked clean.
+    clean: Option<bool>,
+}
+
+impl<A: Frames, B: Frames> Chain<A, B> {
+    /// Whether the first frames are still played.
+    #[must_use]
+    pub fn is_playing_first(&self) -> bool {
+        self.playing_first
+    }
+
+    /// The frames played once.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// The frames looped after the first ones.
+    pub fn then(&self) -> &B {
+        &self.then
+    }
+
+    fn current(&self) -> &dyn Frames {
+        if self.playing_first {
+            &self.first
+        } else {
+            &self.then
+        }
+    }
+
+    /// Advance the current frames, at the given time if any.
+    fn step(&mut self, now: Option<Instant>) {
+        if self.playing_first {
+            if self.advanced + 1 >= self.first.frame_count().unwrap_or(1) {
+                self.playing_first = false;
+                self.then.reset();
+                return;
+            }
+            self.advanced += 1;
+        }
+
+        let frames: &mut dyn Frames = if self.playing_first {
+            &mut self.first
+        } else {
+            &mut self.then
+        };
+        match now {
+            Some(now) => frames.advance_at(now),
+            None => frames.advance(),
+        }
+    }
+}
+
+impl<A: Frames, B: Frames> core::fmt::Display for Chain<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.current().fmt(f)
+    }
+}
+
+impl<A: Frames, B: Frames> Frames for Chain<A, B> {
+    fn advance(&mut self) {
+        self.step(None);
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.step(Some(now));
+    }
+
+    fn reset(&mut self) {
+        self.advanced = 0;
+        self.playing_first = true;
+        self.first.reset();
+        self.then.reset();
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some(self.playing_first) || self.current().is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = Some(self.playing_first);
+        if self.playing_first {
+            self.first.mark_clean();
+        } else {
+            self.then.mark_clean();
+        }
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        None
+    }
+
+    forward_frames!(
+        current();
+        render,
+        repaint,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        state,
+        is_finished,
+        progress,
+        print_len,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`map`].
+#[must_use]
+pub struct Map<F, M> {
+    frames: F,
+    f: M,
+}
+
+impl<F, M> Map<F, M> {
+    /// The transformed frames.
+    pub fn inner(&self) -> &F {
+        &self.frames
+    }
+
+    /// The transformed frames.
+    pub fn inner_mut(&mut self) -> &mut F {
+        &mut self.frames
+    }
+}
+
+impl<F: core::fmt::Debug, M> core::fmt::Debug for Map<F, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Map")
+            .field("frames", &self.frames)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, M> core::fmt::Display for Map<F, M>
+where
+    F: Frames,
+    M: Fn(&dyn core::fmt::Display, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.f)(&self.frames, f)
+    }
+}
+
+impl<F, M> Frames for Map<F, M>
+where
+    F: Frames,
+    M: Fn(&dyn core::fmt::Display, &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+        + Send
+        + Sync
+        + 'static,
+{
+    // The length is not forwarded,
+    // as the function can change it.
+    forward_frames!(
+        frames;
+        advance,
+        advance_at,
+        reset,
+        is_dirty,
+        mark_clean,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        frame_count,
+        state,
+        is_finished,
+        progress,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`once`].
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct OneShot<F> {
+    frames: F,
+    advanced: usize,
+    /// Whether the last frame was marked clean.
+    clean: bool,
+}
+
+impl<F: Frames> OneShot<F> {
+    /// The played frames.
+    pub fn inner(&self) -> &F {
+        &self.frames
+    }
+
+    /// The played frames.
+    pub fn inner_mut(&mut self) -> &mut F {
+        &mut self.frames
+    }
+
+    /// Whether the last frame is displayed.
+    fn is_last(&self) -> bool {
+        match self.frames.frame_count() {
+            Some(count) => self.advanced + 1 >= count,
+            None => self.frames.is_finished(),
+        }
+    }
+}
+
+impl<F: Frames> core::fmt::Display for OneShot<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.frames.fmt(f)
+    }
+}
+
+impl<F: Frames> Frames for OneShot<F> {
+    fn advance(&mut self) {
+        if !self.is_last() {
+            self.frames.advance();
+            self.advanced += 1;
+        }
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        if !self.is_last() {
+            self.frames.advance_at(now);
+            self.advanced += 1;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.advanced = 0;
+        self.clean = false;
+        self.frames.reset();
+    }
+
+    fn is_dirty(&self) -> bool {
+        !(self.is_last() && self.clean) && self.frames.is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = self.is_last();
+        self.frames.mark_clean();
+    }
+
+    fn state(&self) -> crate::FrameState {
+        if self.is_last() && !self.frames.is_finished() {
+            crate::FrameState::Done
+        } else {
+            self.frames.state()
+        }
+    }
+
+    forward_frames!(
+        frames;
+        render,
+        repaint,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        frame_count,
+        progress,
+        print_len,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`shuffle`].
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Shuffle<F> {
+    frames: F,
+    /// The indices of the frames in the order of the current cycle.
+    order: Vec<usize>,
+    /// The position in the order.
+    pos: usize,
+    /// The index of the frame the inner frames are at.
+    at: usize,
+    rng: u64,
+}
+
+impl<F: Frames> Shuffle<F> {
+    /// Shuffle the frames with the given seed instead of
+    /// a random one, the same seed always results
+    /// in the same order.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = seed;
+        self.order.sort_unstable();
+        self.restart();
+        self
+    }
+
+    /// The shuffled frames.
+    pub fn inner(&self) -> &F {
+        &self.frames
+    }
+
+    /// The shuffled frames.
+    pub fn inner_mut(&mut self) -> &mut F {
+        &mut self.frames
+    }
+
+    /// Reset the inner frames and start a new cycle.
+    fn restart(&mut self) {
+        self.frames.reset();
+        self.at = 0;
+        self.pos = 0;
+        self.reorder();
+        self.seek(None);
+    }
+
+    /// Shuffle the order of the next cycle.
+    fn reorder(&mut self) {
+        let last = self.order.last().copied();
+
+        for i in (1..self.order.len()).rev() {
+            #[allow(clippy::cast_possible_truncation)]
+            let j = (next_random(&mut self.rng) % (i as u64 + 1)) as usize;
+            self.order.swap(i, j);
+        }
+
+        // Avoid displaying the same frame twice in a row.
+        if self.order.len() > 1 && self.order.first().copied() == last {
+            self.order.swap(0, 1);
+        }
+    }
+
+    /// Advance the inner frames to the frame at the current position.
+    fn seek(&mut self, now: Option<Instant>) {
+        let Some(&target) = self.order.get(self.pos) else {
+            return;
+        };
+
+        while self.at != target {
+            match now {
+                Some(now) => self.frames.advance_at(now),
+                None => self.frames.advance(),
+            }
+            self.at = (self.at + 1) % self.order.len();
+        }
+    }
+
+    fn step(&mut self, now: Option<Instant>) {
+        self.pos += 1;
+        if self.pos >= self.order.len() {
+            self.pos = 0;
+            self.reorder();
+        }
+        self.seek(now);
+    }
+}
+
+/// A random seed for [`Shuffle`].
+fn random_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+/// The next number of a `SplitMix64` generator.
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl<F: Frames> core::fmt::Display for Shuffle<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.frames.fmt(f)
+    }
+}
+
+impl<F: Frames> Frames for Shuffle<F> {
+    fn advance(&mut self) {
+        self.step(None);
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.step(Some(now));
+    }
+
+    fn reset(&mut self) {
+        self.frames.reset();
+        self.at = 0;
+        self.pos = 0;
+        self.seek(None);
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        Some(self.order.len())
+    }
+
+    forward_frames!(
+        frames;
+        render,
+        repaint,
+        is_dirty,
+        mark_clean,
+        clear,
+        rewind,
+        lines,
+        total_lines,
+        displayed_lines,
+        lines_changed,
+        state,
+        is_finished,
+        progress,
+        print_len,
+        interval,
+        label,
+        for_each_child,
+    );
+}
+
+/// Frames returned by [`from_fn`].
+#[must_use]
+pub struct FromFn<M> {
+    tick: usize,
+    f: M,
+}
+
+impl<M> FromFn<M> {
+    /// The amount of advances since the start.
+    #[must_use]
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+}
+
+impl<M> core::fmt::Debug for FromFn<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FromFn")
+            .field("tick", &self.tick)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M> core::fmt::Display for FromFn<M>
+where
+    M: Fn(usize, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.f)(self.tick, f)
+    }
+}
+
+impl<M> Frames for FromFn<M>
+where
+    M: Fn(usize, &mut core::fmt::Formatter<'_>) -> core::fmt::Result + Send + Sync + 'static,
+{
+    fn advance(&mut self) {
+        self.tick = self.tick.wrapping_add(1);
+    }
+
+    fn reset(&mut self) {
+        self.tick = 0;
     }
 }
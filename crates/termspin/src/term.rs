@@ -0,0 +1,180 @@
+//! Helpers for querying the terminal.
+
+use std::io::{self, IsTerminal};
+
+/// The capabilities of the output that frames are printed to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TermMode {
+    /// Escape sequences are supported.
+    #[default]
+    Ansi,
+    /// Only carriage returns are supported, frames are
+    /// printed on a single line without escape sequences.
+    CarriageReturn,
+    /// The output is not a terminal, nothing is printed.
+    Plain,
+    /// The output is read by programs, frames are not printed
+    /// and state changes of labeled frames are printed as JSON
+    /// lines instead, e.g.
+    /// `{"task":"build","state":"done","elapsed_ms":3200}`.
+    ///
+    /// The elapsed time is only known for lines.
+    JsonLines,
+    /// The output is a GitHub Actions log, frames are not printed
+    /// and labeled frames are printed as collapsible groups
+    /// that start and end with the frames, failed frames
+    /// are also reported as errors.
+    ///
+    /// Groups cannot be nested in the log, so this works
+    /// best if the frames run one after another.
+    GithubActions,
+}
+
+impl TermMode {
+    /// Guess the capabilities of the terminal attached to
+    /// the standard output or standard error from the environment.
+    ///
+    /// Logs of GitHub Actions are detected by the
+    /// `GITHUB_ACTIONS` environment variable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::{spinner, term::TermMode, Line, Loop};
+    /// # use std::time::Duration;
+    /// let l = Loop::new(Duration::from_millis(100), Line::new(spinner::dots()));
+    /// l.term_mode(TermMode::detect());
+    /// ```
+    #[must_use]
+    pub fn detect() -> Self {
+        if std::env::var_os("GITHUB_ACTIONS").is_some_and(|v| v == "true") {
+            return TermMode::GithubActions;
+        }
+
+        if !io::stdout().is_terminal() && !io::stderr().is_terminal() {
+            return TermMode::Plain;
+        }
+
+        match std::env::var("TERM") {
+            Ok(term) if term == "dumb" => TermMode::CarriageReturn,
+            Err(_) if cfg!(unix) => TermMode::CarriageReturn,
+            _ => TermMode::Ansi,
+        }
+    }
+}
+
+/// The width of the terminal in columns if it can be determined.
+///
+/// The terminal attached to the standard output or standard error
+/// is queried first, falling back to the `COLUMNS` environment variable.
+#[must_use]
+pub fn width() -> Option<usize> {
+    sys::width()
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .filter(|&width| width > 0)
+}
+
+/// Whether the process is in the foreground process group
+/// of the terminal attached to the standard output or standard error,
+/// `None` if neither is a terminal.
+///
+/// A process is in the background e.g. after being suspended
+/// with `Ctrl-Z` and continued with `bg`.
+#[must_use]
+pub fn is_foreground() -> Option<bool> {
+    sys::is_foreground()
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly"
+))]
+mod sys {
+    use std::os::raw::{c_int, c_ulong, c_ushort};
+
+    #[cfg(all(
+        any(target_os = "linux", target_os = "android"),
+        not(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        ))
+    ))]
+    const TIOCGWINSZ: c_ulong = 0x5413;
+
+    #[cfg(not(all(
+        any(target_os = "linux", target_os = "android"),
+        not(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64"
+        ))
+    )))]
+    const TIOCGWINSZ: c_ulong = 0x4008_7468;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct WinSize {
+        rows: c_ushort,
+        cols: c_ushort,
+        x_pixels: c_ushort,
+        y_pixels: c_ushort,
+    }
+
+    extern "C" {
+        fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+        fn tcgetpgrp(fd: c_int) -> c_int;
+        fn getpgrp() -> c_int;
+    }
+
+    pub(super) fn width() -> Option<usize> {
+        [1, 2].into_iter().find_map(|fd| {
+            let mut size = WinSize::default();
+
+            // SAFETY: `TIOCGWINSZ` only writes a `winsize`
+            // struct to the given pointer.
+            let res = unsafe { ioctl(fd, TIOCGWINSZ, std::ptr::addr_of_mut!(size)) };
+
+            (res == 0 && size.cols > 0).then_some(usize::from(size.cols))
+        })
+    }
+
+    pub(super) fn is_foreground() -> Option<bool> {
+        [1, 2].into_iter().find_map(|fd| {
+            // SAFETY: both functions only read process state.
+            let (fg, own) = unsafe { (tcgetpgrp(fd), getpgrp()) };
+
+            (fg >= 0).then_some(fg == own)
+        })
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "dragonfly"
+)))]
+mod sys {
+    pub(super) fn width() -> Option<usize> {
+        None
+    }
+
+    pub(super) fn is_foreground() -> Option<bool> {
+        None
+    }
+}
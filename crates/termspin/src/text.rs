@@ -0,0 +1,524 @@
+//! Styled text made up of spans.
+//!
+//! # Example
+//!
+//! ```
+//! # use termspin::{spinner, Line};
+//! # use termspin::text::{Color, Span, Style, Text};
+//! let text = Text::new()
+//!     .with_span(Span::styled("error", Style::new().with_fg(Color::Red).with_bold(true)))
+//!     .with_span(Span::new(": connection refused"));
+//!
+//! let line = Line::new(spinner::dots()).with_styled_text(&text);
+//! ```
+
+use std::borrow::Cow;
+
+use crate::{
+    ansi::ResetStyle,
+    util::{char_width, display_width, Token, Tokens},
+};
+
+/// A terminal color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// Black.
+    Black,
+    /// Red.
+    Red,
+    /// Green.
+    Green,
+    /// Yellow.
+    Yellow,
+    /// Blue.
+    Blue,
+    /// Magenta.
+    Magenta,
+    /// Cyan.
+    Cyan,
+    /// White.
+    White,
+    /// A color of the 256-color palette.
+    Ansi256(u8),
+    /// A 24-bit color.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Write the SGR parameters of the color,
+    /// `base` is either `30` (foreground) or `40` (background).
+    fn fmt_params(self, base: u8, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let offset = match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::Ansi256(n) => return write!(f, "{};5;{n}", base + 8),
+            Color::Rgb(r, g, b) => return write!(f, "{};2;{r};{g};{b}", base + 8),
+        };
+
+        write!(f, "{}", base + offset)
+    }
+}
+
+const BOLD: u8 = 1 << 0;
+const DIM: u8 = 1 << 1;
+const ITALIC: u8 = 1 << 2;
+const UNDERLINE: u8 = 1 << 3;
+
+/// The style of a [`Span`].
+///
+/// Displaying a style prints the SGR escape sequence
+/// that enables it, nothing is printed for the default style.
+#[must_use]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Style {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    attrs: u8,
+}
+
+impl Style {
+    /// Create a style without any colors or attributes.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            attrs: 0,
+        }
+    }
+
+    /// Set the foreground color.
+    pub const fn with_fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Set the background color.
+    pub const fn with_bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Toggle bold text.
+    pub const fn with_bold(self, bold: bool) -> Self {
+        self.with_attr(BOLD, bold)
+    }
+
+    /// Toggle dimmed text.
+    pub const fn with_dim(self, dim: bool) -> Self {
+        self.with_attr(DIM, dim)
+    }
+
+    /// Toggle italic text.
+    pub const fn with_italic(self, italic: bool) -> Self {
+        self.with_attr(ITALIC, italic)
+    }
+
+    /// Toggle underlined text.
+    pub const fn with_underline(self, underline: bool) -> Self {
+        self.with_attr(UNDERLINE, underline)
+    }
+
+    /// The foreground color.
+    #[must_use]
+    pub const fn fg(&self) -> Option<Color> {
+        self.fg
+    }
+
+    /// The background color.
+    #[must_use]
+    pub const fn bg(&self) -> Option<Color> {
+        self.bg
+    }
+
+    /// Whether the text is bold.
+    #[must_use]
+    pub const fn is_bold(&self) -> bool {
+        self.attrs & BOLD != 0
+    }
+
+    /// Whether the text is dimmed.
+    #[must_use]
+    pub const fn is_dim(&self) -> bool {
+        self.attrs & DIM != 0
+    }
+
+    /// Whether the text is italic.
+    #[must_use]
+    pub const fn is_italic(&self) -> bool {
+        self.attrs & ITALIC != 0
+    }
+
+    /// Whether the text is underlined.
+    #[must_use]
+    pub const fn is_underline(&self) -> bool {
+        self.attrs & UNDERLINE != 0
+    }
+
+    /// Whether the style has no colors or attributes.
+    #[must_use]
+    pub const fn is_plain(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.attrs == 0
+    }
+
+    const fn with_attr(mut self, attr: u8, enabled: bool) -> Self {
+        if enabled {
+            self.attrs |= attr;
+        } else {
+            self.attrs &= !attr;
+        }
+        self
+    }
+}
+
+impl core::fmt::Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_plain() {
+            return Ok(());
+        }
+
+        f.write_str("\x1B[")?;
+
+        let mut first = true;
+        let mut sep = |f: &mut std::fmt::Formatter<'_>| {
+            if !std::mem::take(&mut first) {
+                f.write_str(";")?;
+            }
+            Ok(())
+        };
+
+        for (attr, param) in [(BOLD, "1"), (DIM, "2"), (ITALIC, "3"), (UNDERLINE, "4")] {
+            if self.attrs & attr != 0 {
+                sep(f)?;
+                f.write_str(param)?;
+            }
+        }
+        if let Some(fg) = self.fg {
+            sep(f)?;
+            fg.fmt_params(30, f)?;
+        }
+        if let Some(bg) = self.bg {
+            sep(f)?;
+            bg.fmt_params(40, f)?;
+        }
+
+        f.write_str("m")
+    }
+}
+
+/// Text with a single style.
+///
+/// Escape sequences and control characters
+/// in the content are not displayed.
+#[must_use]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    content: Cow<'static, str>,
+    style: Style,
+}
+
+impl Span {
+    /// Create a span without any style.
+    pub fn new(content: &str) -> Self {
+        Self::styled(content, Style::new())
+    }
+
+    /// Create a span with the given style.
+    pub fn styled(content: &str, style: Style) -> Self {
+        Self {
+            content: content.to_string().into(),
+            style,
+        }
+    }
+
+    /// Set the style of the span.
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the span.
+    pub fn set_style(&mut self, style: Style) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// The content of the span.
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// The style of the span.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// The amount of columns the span occupies in the terminal.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        visible(&self.content).map(char_width).sum()
+    }
+
+    /// Shorten the span to at most the given width.
+    fn truncate(&mut self, width: usize) {
+        let mut used = 0;
+        let content: String = visible(&self.content)
+            .take_while(|&c| {
+                used += char_width(c);
+                used <= width
+            })
+            .collect();
+
+        self.content = content.into();
+    }
+}
+
+impl core::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.style.fmt(f)?;
+
+        for token in Tokens(&self.content) {
+            if let Token::Text(text) = token {
+                f.write_str(text)?;
+            }
+        }
+
+        if !self.style.is_plain() {
+            ResetStyle.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for Span {
+    fn from(content: &str) -> Self {
+        Self::new(content)
+    }
+}
+
+/// Text made up of [`Span`]s with different styles.
+#[must_use]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Text {
+    spans: Vec<Span>,
+}
+
+impl Text {
+    /// Create empty text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a span.
+    pub fn with_span(mut self, span: impl Into<Span>) -> Self {
+        self.push(span);
+        self
+    }
+
+    /// Append a span.
+    pub fn push(&mut self, span: impl Into<Span>) -> &mut Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    /// The spans of the text.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// The amount of columns the text occupies in the terminal.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.spans.iter().map(Span::width).sum()
+    }
+
+    /// Shorten the text to at most the given width,
+    /// the styles of the remaining spans are kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::text::{Color, Span, Style, Text};
+    /// let mut text = Text::new()
+    ///     .with_span(Span::styled("hello", Style::new().with_fg(Color::Green)))
+    ///     .with_span(" world");
+    ///
+    /// text.truncate(7);
+    /// assert_eq!(text.width(), 7);
+    /// assert_eq!(text.spans()[1].content(), " w");
+    /// ```
+    pub fn truncate(&mut self, width: usize) -> &mut Self {
+        let mut remaining = width;
+
+        for (idx, span) in self.spans.iter_mut().enumerate() {
+            let span_width = span.width();
+
+            if span_width > remaining {
+                span.truncate(remaining);
+                self.spans.truncate(idx + 1);
+                break;
+            }
+
+            remaining -= span_width;
+        }
+
+        self.spans.retain(|span| !span.content.is_empty());
+        self
+    }
+}
+
+impl core::fmt::Display for Text {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for span in &self.spans {
+            span.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for Text {
+    fn from(content: &str) -> Self {
+        Self::new().with_span(content)
+    }
+}
+
+impl From<Span> for Text {
+    fn from(span: Span) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl FromIterator<Span> for Text {
+    fn from_iter<I: IntoIterator<Item = Span>>(iter: I) -> Self {
+        Self {
+            spans: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// The displayed characters of the text without
+/// escape sequences and control characters.
+fn visible(text: &str) -> impl Iterator<Item = char> + '_ {
+    Tokens(text)
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text),
+            Token::Escape(_) | Token::Control(_) => None,
+        })
+        .flat_map(str::chars)
+}
+
+/// Shorten a file path to at most the given width.
+///
+/// Directories after the first component are replaced with `…`
+/// until the path fits, so that the start of the path and the
+/// file name stay recognizable. If even that is too long, only
+/// the end of the file name is kept.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::text::truncate_path;
+/// let path = "~/code/work/project/src/main.rs";
+///
+/// assert_eq!(truncate_path(path, 40), path);
+/// assert_eq!(truncate_path(path, 24), "~/…/project/src/main.rs");
+/// assert_eq!(truncate_path(path, 10), "…/main.rs");
+/// assert_eq!(truncate_path(path, 5), "…n.rs");
+/// ```
+#[must_use]
+pub fn truncate_path(path: &str, width: usize) -> Cow<'_, str> {
+    if display_width(path) <= width {
+        return Cow::Borrowed(path);
+    }
+
+    let sep = if path.contains('/') { '/' } else { '\\' };
+    let parts: Vec<&str> = path.split(sep).collect();
+    let (first, rest) = (parts[0], &parts[1..]);
+
+    for skip in 1..rest.len() {
+        let mut shortened = format!("{first}{sep}…");
+        for part in &rest[skip..] {
+            shortened.push(sep);
+            shortened.push_str(part);
+        }
+
+        if display_width(&shortened) <= width {
+            return shortened.into();
+        }
+    }
+
+    let name = parts[parts.len() - 1];
+    let shortened = format!("…{sep}{name}");
+    if display_width(&shortened) <= width {
+        return shortened.into();
+    }
+
+    truncate_start(name, width).into()
+}
+
+/// Whether the text is likely a file path.
+pub(crate) fn is_path_like(text: &str) -> bool {
+    (text.contains('/') || text.contains('\\'))
+        && !text.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+/// Shorten the text to at most the given width by
+/// replacing its end with `…`, escape sequences are kept.
+pub(crate) fn truncate_end(text: &str, width: usize) -> String {
+    let mut shortened = String::with_capacity(text.len());
+    let mut used = 0;
+    let mut full = false;
+
+    for token in Tokens(text) {
+        match token {
+            Token::Text(_) if full => {}
+            Token::Text(text) => {
+                for c in text.chars() {
+                    if used + char_width(c) + 1 > width {
+                        if width > 0 {
+                            shortened.push('…');
+                        }
+                        full = true;
+                        break;
+                    }
+
+                    used += char_width(c);
+                    shortened.push(c);
+                }
+            }
+            Token::Escape(seq) => shortened.push_str(seq),
+            Token::Control(c) => shortened.push(c),
+        }
+    }
+
+    shortened
+}
+
+/// Shorten the text to at most the given width by
+/// replacing its start with `…`.
+fn truncate_start(text: &str, width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut used = 1;
+    let mut kept: Vec<char> = text
+        .chars()
+        .rev()
+        .take_while(|&c| {
+            used += char_width(c);
+            used <= width
+        })
+        .collect();
+    kept.push('…');
+
+    kept.into_iter().rev().collect()
+}
@@ -0,0 +1,326 @@
+//! Time-based [`Frames`] segments and duration formatting.
+
+use std::{
+    borrow::Cow,
+    fmt::Write,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{util::DisplayFn, Frames};
+
+/// Create a segment that displays the time elapsed since its creation.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::time::{elapsed, DurationFormat};
+/// elapsed().with_format(DurationFormat::Clock);
+/// ```
+pub fn elapsed() -> Elapsed {
+    Elapsed::new()
+}
+
+/// Create a segment that displays the current UTC time
+/// formatted as `%H:%M:%S`.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::time::clock;
+/// clock().with_format("%Y-%m-%d %H:%M");
+/// ```
+pub fn clock() -> Clock {
+    Clock::new()
+}
+
+/// The way durations are displayed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationFormat {
+    /// A compact format, e.g. `1m03s`.
+    #[default]
+    Compact,
+    /// A clock-like format, e.g. `00:01:03`.
+    Clock,
+    /// A rough human-readable format, e.g. `about a minute`.
+    Humanized,
+}
+
+impl DurationFormat {
+    /// Display the given duration in this format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use termspin::time::DurationFormat;
+    /// # use std::time::Duration;
+    /// let d = Duration::from_secs(63);
+    ///
+    /// assert_eq!(DurationFormat::Compact.display(d).to_string(), "1m03s");
+    /// assert_eq!(DurationFormat::Clock.display(d).to_string(), "00:01:03");
+    /// assert_eq!(DurationFormat::Humanized.display(d).to_string(), "about a minute");
+    /// ```
+    #[must_use]
+    pub fn display(self, duration: Duration) -> impl core::fmt::Display {
+        DisplayFn::new(move |f| self.fmt(duration, f))
+    }
+
+    fn fmt(self, duration: Duration, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = duration.as_secs();
+        let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+        match self {
+            DurationFormat::Compact => {
+                if h > 0 {
+                    write!(f, "{h}h{m:02}m{s:02}s")
+                } else if m > 0 {
+                    write!(f, "{m}m{s:02}s")
+                } else {
+                    write!(f, "{s}s")
+                }
+            }
+            DurationFormat::Clock => write!(f, "{h:02}:{m:02}:{s:02}"),
+            DurationFormat::Humanized => match secs {
+                0 => f.write_str("less than a second"),
+                1 => f.write_str("1 second"),
+                2..=59 => write!(f, "{secs} seconds"),
+                60..=89 => f.write_str("about a minute"),
+                90..=2699 => write!(f, "{} minutes", (secs + 30) / 60),
+                2700..=5399 => f.write_str("about an hour"),
+                5400..=79199 => write!(f, "{} hours", (secs + 1800) / 3600),
+                79200..=129_599 => f.write_str("about a day"),
+                _ => write!(f, "{} days", (secs + 43200) / 86400),
+            },
+        }
+    }
+}
+
+/// Frames returned by [`elapsed`].
+///
+/// Resetting the frames restarts the timer.
+///
+/// The frames are only [dirty](Frames::is_dirty) when
+/// the displayed whole seconds change.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{time::elapsed, Frames};
+/// # use std::time::{Duration, Instant};
+/// let now = Instant::now();
+/// let mut elapsed = elapsed();
+///
+/// elapsed.advance_at(now);
+/// elapsed.mark_clean();
+/// assert!(!elapsed.is_dirty());
+///
+/// elapsed.advance_at(now + Duration::from_secs(2));
+/// assert!(elapsed.is_dirty());
+/// ```
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct Elapsed {
+    start: Instant,
+    /// The time of the last [`Frames::advance_at`], if any.
+    now: Option<Instant>,
+    format: DurationFormat,
+    /// The whole seconds when the frames were marked clean.
+    clean: Option<u64>,
+}
+
+impl Default for Elapsed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Elapsed {
+    /// Create a new segment that starts counting now.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            now: None,
+            format: DurationFormat::default(),
+            clean: None,
+        }
+    }
+
+    /// The time elapsed since the start.
+    ///
+    /// After [`Frames::advance_at`] the elapsed time is measured
+    /// until the given time instead of the current one.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.now
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.start)
+    }
+
+    /// Set the format of the displayed duration.
+    pub fn with_format(mut self, format: DurationFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the format of the displayed duration.
+    pub fn set_format(&mut self, format: DurationFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+}
+
+impl core::fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.format.fmt(self.elapsed(), f)
+    }
+}
+
+impl Frames for Elapsed {
+    fn advance(&mut self) {
+        self.now = None;
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.now = Some(now);
+    }
+
+    fn reset(&mut self) {
+        self.start = Instant::now();
+        self.now = None;
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(1))
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some(self.elapsed().as_secs())
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = Some(self.elapsed().as_secs());
+    }
+}
+
+/// Frames returned by [`clock`].
+///
+/// The format supports the following `strftime`-like specifiers,
+/// any other characters are displayed as-is:
+///
+/// - `%Y`: the year, e.g. `2024`
+/// - `%m`: the month, `01` to `12`
+/// - `%d`: the day of the month, `01` to `31`
+/// - `%H`: the hour, `00` to `23`
+/// - `%M`: the minute, `00` to `59`
+/// - `%S`: the second, `00` to `59`
+/// - `%%`: a literal `%`
+///
+/// Only UTC time is supported.
+///
+/// The frames are only [dirty](Frames::is_dirty) when
+/// the current whole seconds change.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Clock {
+    format: Cow<'static, str>,
+    /// The whole seconds when the frames were marked clean.
+    clean: Option<u64>,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock {
+    /// Create a new clock formatted as `%H:%M:%S`.
+    pub fn new() -> Self {
+        Self {
+            format: Cow::Borrowed("%H:%M:%S"),
+            clean: None,
+        }
+    }
+
+    /// Set the format of the displayed time.
+    pub fn with_format(mut self, format: &str) -> Self {
+        self.format = format.to_string().into();
+        self
+    }
+
+    /// Set the format of the displayed time.
+    pub fn set_format(&mut self, format: &str) -> &mut Self {
+        self.format = format.to_string().into();
+        self
+    }
+}
+
+impl core::fmt::Display for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let secs = unix_secs();
+        let (year, month, day) = civil_from_days(secs / 86400);
+        let (hour, minute, second) = (secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+        let mut chars = self.format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                f.write_char(c)?;
+                continue;
+            }
+
+            match chars.next() {
+                Some('Y') => write!(f, "{year}")?,
+                Some('m') => write!(f, "{month:02}")?,
+                Some('d') => write!(f, "{day:02}")?,
+                Some('H') => write!(f, "{hour:02}")?,
+                Some('M') => write!(f, "{minute:02}")?,
+                Some('S') => write!(f, "{second:02}")?,
+                Some('%') | None => f.write_char('%')?,
+                Some(other) => {
+                    f.write_char('%')?;
+                    f.write_char(other)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Frames for Clock {
+    fn advance(&mut self) {}
+
+    fn interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(1))
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some(unix_secs())
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = Some(unix_secs());
+    }
+}
+
+/// The whole seconds since the Unix epoch.
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Convert days since the Unix epoch to a `(year, month, day)` date.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
@@ -0,0 +1,110 @@
+//! A Linux timer file descriptor for [`Loop::poll`](crate::Loop::poll).
+
+use std::{
+    io,
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+        raw::{c_int, c_long},
+    },
+    sync::Arc,
+    time::Duration,
+};
+
+const CLOCK_MONOTONIC: c_int = 1;
+const TFD_NONBLOCK: c_int = 0o4000;
+const TFD_CLOEXEC: c_int = 0o2_000_000;
+
+#[repr(C)]
+struct TimeSpec {
+    tv_sec: c_long,
+    tv_nsec: c_long,
+}
+
+#[repr(C)]
+struct ITimerSpec {
+    it_interval: TimeSpec,
+    it_value: TimeSpec,
+}
+
+extern "C" {
+    fn timerfd_create(clockid: c_int, flags: c_int) -> c_int;
+    fn timerfd_settime(
+        fd: c_int,
+        flags: c_int,
+        new_value: *const ITimerSpec,
+        old_value: *mut ITimerSpec,
+    ) -> c_int;
+}
+
+/// A file descriptor that becomes readable when the next
+/// tick of a [`Loop`](crate::Loop) is due, see
+/// [`Loop::timer_fd`](crate::Loop::timer_fd).
+///
+/// It can be registered with `epoll`, `mio` or similar,
+/// and is re-armed by [`Loop::poll`](crate::Loop::poll).
+#[derive(Debug, Clone)]
+pub struct TimerFd {
+    fd: Arc<OwnedFd>,
+}
+
+impl TimerFd {
+    pub(crate) fn new() -> io::Result<Self> {
+        // SAFETY: only creates a new file descriptor.
+        let fd = unsafe { timerfd_create(CLOCK_MONOTONIC, TFD_NONBLOCK | TFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            // SAFETY: the file descriptor was just created and is not owned elsewhere.
+            fd: Arc::new(unsafe { OwnedFd::from_raw_fd(fd) }),
+        })
+    }
+
+    /// Make the descriptor readable after the given delay,
+    /// which also clears the previous expiration.
+    pub(crate) fn arm(&self, delay: Duration) -> io::Result<()> {
+        // A zero value would disarm the timer.
+        let delay = delay.max(Duration::from_nanos(1));
+        // `c_long` is only 32 bits wide on some targets.
+        #[allow(clippy::unnecessary_fallible_conversions)]
+        let spec = ITimerSpec {
+            it_interval: TimeSpec {
+                tv_sec: 0,
+                tv_nsec: 0,
+            },
+            it_value: TimeSpec {
+                tv_sec: c_long::try_from(delay.as_secs()).unwrap_or(c_long::MAX),
+                tv_nsec: c_long::try_from(delay.subsec_nanos()).unwrap_or_default(),
+            },
+        };
+
+        // SAFETY: `spec` is a valid `itimerspec` and
+        // the old value is not requested.
+        let res = unsafe {
+            timerfd_settime(
+                self.fd.as_raw_fd(),
+                0,
+                std::ptr::addr_of!(spec),
+                std::ptr::null_mut(),
+            )
+        };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+}
+
+impl AsFd for TimerFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl AsRawFd for TimerFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    ansi::CursorUp, average_progress, fmt_child, render_child, repaint_child, shortest_interval,
+    util::Cadence, FrameState, Frames, SharedFrames,
+};
+
+/// A group of frames of the same type
+/// that are separated by new lines.
+///
+/// Unlike [`Group`](crate::Group), the children are stored
+/// contiguously without boxing, which is faster for
+/// groups with a large amount of children.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Line, TypedGroup};
+/// let mut group = TypedGroup::with_capacity(1000);
+///
+/// for i in 0..1000 {
+///     group.push(Line::new(spinner::dots()).with_text(&format!("package {i}")));
+/// }
+///
+/// group.iter_mut().for_each(|line| {
+///     line.set_spinner_visible(false);
+/// });
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct TypedGroup<T> {
+    indent: usize,
+    frames: Vec<T>,
+    cadence: Cadence,
+    /// The indentation and the amount of children
+    /// when they were marked clean.
+    clean: Option<(usize, usize)>,
+}
+
+impl<T> Default for TypedGroup<T> {
+    fn default() -> Self {
+        Self {
+            indent: 0,
+            frames: Vec::new(),
+            cadence: Cadence::default(),
+            clean: None,
+        }
+    }
+}
+
+impl<T: Frames> TypedGroup<T> {
+    /// Create a new empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new empty group with space
+    /// for at least the given amount of items.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            indent: 0,
+            frames: Vec::with_capacity(capacity),
+            cadence: Cadence::default(),
+            clean: None,
+        }
+    }
+
+    /// The amount of items in this group.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether the group has no children.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Insert an item at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, idx: usize, frames: T) -> &mut Self {
+        self.frames.insert(idx, frames);
+        self
+    }
+
+    /// Push an item at the end.
+    pub fn push(&mut self, frames: T) -> &mut Self {
+        self.frames.push(frames);
+        self
+    }
+
+    /// Extend this group from an iterator.
+    pub fn extend(&mut self, iter: impl IntoIterator<Item = T>) -> &mut Self {
+        self.frames.extend(iter);
+        self
+    }
+
+    /// Remove an item at the given position and return it,
+    /// or `None` if the position is invalid.
+    pub fn try_remove(&mut self, idx: usize) -> Option<T> {
+        (idx < self.frames.len()).then(|| self.frames.remove(idx))
+    }
+
+    /// Return the item at the given position.
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.frames.get(idx)
+    }
+
+    /// Return the item at the given position.
+    #[must_use]
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.frames.get_mut(idx)
+    }
+
+    /// Return an iterator of the frames in this group.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.frames.iter()
+    }
+
+    /// Return an iterator of the frames in this group.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.frames.iter_mut()
+    }
+
+    /// Retains only the items specified by the predicate.
+    pub fn retain(&mut self, f: impl FnMut(&mut T) -> bool) {
+        self.frames.retain_mut(f);
+    }
+
+    /// Return the indentation level of this group.
+    #[must_use]
+    pub fn get_indent(&self) -> usize {
+        self.indent
+    }
+
+    /// Set the indentation level of this group.
+    pub fn with_indent(mut self, level: usize) -> Self {
+        self.indent = level;
+        self
+    }
+
+    /// Set the indentation level of this group.
+    pub fn set_indent(&mut self, level: usize) -> &mut Self {
+        self.indent = level;
+        self
+    }
+
+    /// Turn this group into [`SharedFrames`].
+    pub fn shared(self) -> SharedFrames<Self> {
+        SharedFrames::new(self)
+    }
+}
+
+impl<T: Frames> core::fmt::Display for TypedGroup<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for spinner in &self.frames {
+            fmt_child(self.indent, 0, spinner, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Frames> Frames for TypedGroup<T> {
+    fn advance(&mut self) {
+        let is_due = self.cadence.tick(Instant::now());
+        for spinner in &mut self.frames {
+            if is_due(spinner.interval()) {
+                spinner.advance();
+            }
+        }
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        let is_due = self.cadence.tick(now);
+        for spinner in &mut self.frames {
+            if is_due(spinner.interval()) {
+                spinner.advance_at(now);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.cadence.reset();
+        for spinner in &mut self.frames {
+            spinner.reset();
+        }
+    }
+
+    fn render(&self, buf: &mut String) {
+        for spinner in &self.frames {
+            render_child(self.indent, 0, spinner, buf);
+        }
+    }
+
+    fn repaint(&self, buf: &mut String) {
+        if self.clean != Some((self.indent, self.frames.len())) {
+            self.render(buf);
+            return;
+        }
+
+        for spinner in &self.frames {
+            repaint_child(self.indent, 0, spinner, buf);
+        }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.clean != Some((self.indent, self.frames.len())) || self.frames.iter().any(T::is_dirty)
+    }
+
+    fn mark_clean(&mut self) {
+        self.clean = Some((self.indent, self.frames.len()));
+        for spinner in &mut self.frames {
+            spinner.mark_clean();
+        }
+    }
+
+    fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        for spinner in self.frames.iter().rev() {
+            CursorUp(spinner.displayed_lines()).fmt(f)?;
+            spinner.clear(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        for spinner in self.frames.iter().rev() {
+            CursorUp(spinner.displayed_lines()).fmt(f)?;
+            spinner.rewind(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn total_lines(&self) -> usize {
+        self.frames.iter().map(Frames::total_lines).sum()
+    }
+
+    fn lines_changed(&self) -> bool {
+        self.frames.iter().any(Frames::lines_changed)
+    }
+
+    fn state(&self) -> FrameState {
+        self.frames
+            .iter()
+            .map(Frames::state)
+            .min()
+            .unwrap_or_default()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        average_progress(self.frames.iter().map(Frames::progress))
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        shortest_interval(self.frames.iter().map(Frames::interval))
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&dyn Frames)) {
+        for spinner in &self.frames {
+            f(spinner);
+        }
+    }
+}
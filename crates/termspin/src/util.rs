@@ -1,7 +1,103 @@
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
+};
 
 use crate::Frames;
 
+/// Decides which children of a container are due to advance
+/// based on their [`Frames::interval`].
+///
+/// Ticks are counted from the first one, a child is due whenever
+/// a multiple of its interval passed since the previous tick.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Cadence {
+    /// The first and the previous tick.
+    ticks: Option<(Instant, Instant)>,
+}
+
+impl Cadence {
+    /// Record a tick at the given time, the returned function
+    /// tells whether a child with the given interval is due.
+    pub(crate) fn tick(&mut self, now: Instant) -> impl Fn(Option<Duration>) -> bool {
+        let prev = self.ticks;
+        self.ticks = Some((prev.map_or(now, |(start, _)| start), now));
+
+        move |interval| match (prev, interval) {
+            (Some((start, last)), Some(interval)) if !interval.is_zero() => {
+                let period = |t: Instant| {
+                    t.saturating_duration_since(start).as_nanos() / interval.as_nanos()
+                };
+                period(now) != period(last)
+            }
+            _ => true,
+        }
+    }
+
+    /// Forget the ticks so far, all children
+    /// are due on the next tick.
+    pub(crate) fn reset(&mut self) {
+        self.ticks = None;
+    }
+}
+
+/// Counts the columns of formatted text like [`display_width`]
+/// without buffering it, escape sequences can be split
+/// across writes.
+#[derive(Debug, Default)]
+pub(crate) struct WidthCounter {
+    width: usize,
+    escape: Escape,
+}
+
+/// The part of an escape sequence written so far.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    #[default]
+    None,
+    Start,
+    Csi,
+    Osc,
+    OscEnd,
+}
+
+impl WidthCounter {
+    /// The amount of columns the displayed value occupies.
+    pub(crate) fn measure(value: &(impl core::fmt::Display + ?Sized)) -> usize {
+        let mut counter = Self::default();
+        let _ = core::fmt::Write::write_fmt(&mut counter, format_args!("{value}"));
+        counter.width
+    }
+}
+
+impl core::fmt::Write for WidthCounter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            self.escape = match (self.escape, c) {
+                (Escape::Start, '[')
+                | (Escape::Csi, '0'..='9' | ';' | ':' | '?' | '<' | '=' | '>') => Escape::Csi,
+                (Escape::Start, ']') | (Escape::Osc, _) if c != '\x07' && c != '\x1B' => {
+                    Escape::Osc
+                }
+                (Escape::Osc, '\x1B') => Escape::OscEnd,
+                (Escape::Start | Escape::Csi | Escape::Osc, _) | (Escape::OscEnd, '\\') => {
+                    Escape::None
+                }
+                (Escape::None | Escape::OscEnd, '\x1B') => Escape::Start,
+                (Escape::None | Escape::OscEnd, c) => {
+                    self.width += char_width(c);
+                    Escape::None
+                }
+            };
+        }
+
+        Ok(())
+    }
+}
+
 pub(crate) struct DisplayFn<F>
 where
     F: Fn(&mut std::fmt::Formatter<'_>) -> core::fmt::Result,
@@ -27,6 +123,255 @@ where
     }
 }
 
+/// Writes indentation after every new line.
+pub(crate) struct Indented<'w, W> {
+    inner: &'w mut W,
+    level: usize,
+}
+
+impl<'w, W> Indented<'w, W>
+where
+    W: core::fmt::Write,
+{
+    pub(crate) fn new(inner: &'w mut W, level: usize) -> Self {
+        Self { inner, level }
+    }
+}
+
+impl<W> core::fmt::Write for Indented<'_, W>
+where
+    W: core::fmt::Write,
+{
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for part in s.split_inclusive('\n') {
+            self.inner.write_str(part)?;
+
+            if part.ends_with('\n') {
+                for _ in 0..self.level {
+                    self.inner.write_str("  ")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A piece of text split by [`Tokens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Token<'s> {
+    /// Printable characters.
+    Text(&'s str),
+    /// A complete or truncated escape sequence.
+    Escape(&'s str),
+    /// A single control character that is not an escape.
+    Control(char),
+}
+
+impl Token<'_> {
+    /// Whether the token is an SGR (styling) escape sequence.
+    pub(crate) fn is_sgr(&self) -> bool {
+        matches!(self, Token::Escape(seq) if seq.starts_with("\x1B[") && seq.ends_with('m'))
+    }
+}
+
+/// Splits text into printable text, escape sequences
+/// and control characters.
+pub(crate) struct Tokens<'s>(pub(crate) &'s str);
+
+impl<'s> Iterator for Tokens<'s> {
+    type Item = Token<'s>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.0;
+        let first = rest.chars().next()?;
+
+        if !first.is_control() {
+            let len = rest.find(char::is_control).unwrap_or(rest.len());
+            self.0 = &rest[len..];
+            return Some(Token::Text(&rest[..len]));
+        }
+
+        if first != '\x1B' {
+            self.0 = &rest[first.len_utf8()..];
+            return Some(Token::Control(first));
+        }
+
+        let len = if let Some(seq) = rest.strip_prefix("\x1B[") {
+            let params_len = seq
+                .find(|c: char| !matches!(c, '0'..='9' | ';' | ':' | '?' | '<' | '=' | '>'))
+                .unwrap_or(seq.len());
+
+            2 + params_len + seq[params_len..].chars().next().map_or(0, char::len_utf8)
+        } else if let Some(seq) = rest.strip_prefix("\x1B]") {
+            // Operating system commands are terminated by BEL or ST.
+            match seq.find(['\x07', '\x1B']) {
+                Some(end) if seq[end..].starts_with("\x1B\\") => 2 + end + 2,
+                Some(end) => 2 + end + 1,
+                None => rest.len(),
+            }
+        } else {
+            // The escape and the character after it.
+            rest.chars().take(2).map(char::len_utf8).sum()
+        };
+
+        self.0 = &rest[len..];
+        Some(Token::Escape(&rest[..len]))
+    }
+}
+
+/// Displays the text without control characters,
+/// only SGR escape sequences are kept.
+pub(crate) struct Sanitized<'s>(pub(crate) &'s str);
+
+impl core::fmt::Display for Sanitized<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for token in Tokens(self.0) {
+            match token {
+                Token::Text(text) => f.write_str(text)?,
+                Token::Escape(seq) if token.is_sgr() => f.write_str(seq)?,
+                Token::Escape(_) | Token::Control(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Displays the bytes encoded as standard padded base64.
+#[cfg(feature = "iterm2")]
+pub(crate) struct Base64<'b>(pub(crate) &'b [u8]);
+
+#[cfg(feature = "iterm2")]
+impl core::fmt::Display for Base64<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        for chunk in self.0.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let idx = (n >> (18 - 6 * i)) & 0x3F;
+                    core::fmt::Write::write_char(f, char::from(ALPHABET[idx as usize]))?;
+                } else {
+                    core::fmt::Write::write_char(f, '=')?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The text on a single line without any
+/// escape sequences or control characters.
+pub(crate) fn single_line(text: &str) -> String {
+    let mut line = String::with_capacity(text.len());
+
+    for token in Tokens(text) {
+        match token {
+            Token::Text(text) => line.push_str(text),
+            Token::Control('\n') => line.push_str("  "),
+            Token::Escape(_) | Token::Control(_) => {}
+        }
+    }
+
+    line.truncate(line.trim_end().len());
+    line
+}
+
+/// The amount of columns the character occupies
+/// in the terminal.
+///
+/// Wide characters, e.g. CJK ideographs and most
+/// emoji occupy two columns, combining marks none.
+pub(crate) fn char_width(c: char) -> usize {
+    const ZERO: &[(u32, u32)] = &[
+        (0x0300, 0x036F),
+        (0x200B, 0x200F),
+        (0x20D0, 0x20FF),
+        (0xFE00, 0xFE0F),
+        (0xFE20, 0xFE2F),
+    ];
+    const WIDE: &[(u32, u32)] = &[
+        (0x1100, 0x115F),
+        (0x231A, 0x231B),
+        (0x23E9, 0x23EC),
+        (0x25FD, 0x25FE),
+        (0x2614, 0x2615),
+        (0x26AA, 0x26AB),
+        (0x26BD, 0x26BE),
+        (0x26F5, 0x26F5),
+        (0x26FA, 0x26FA),
+        (0x2705, 0x2705),
+        (0x270A, 0x270B),
+        (0x274C, 0x274C),
+        (0x2753, 0x2755),
+        (0x2795, 0x2797),
+        (0x2B1B, 0x2B1C),
+        (0x2E80, 0x303E),
+        (0x3041, 0x33FF),
+        (0x3400, 0x4DBF),
+        (0x4E00, 0x9FFF),
+        (0xA000, 0xA4CF),
+        (0xAC00, 0xD7A3),
+        (0xF900, 0xFAFF),
+        (0xFE30, 0xFE4F),
+        (0xFF00, 0xFF60),
+        (0xFFE0, 0xFFE6),
+        (0x1F004, 0x1F004),
+        (0x1F18E, 0x1F18E),
+        (0x1F191, 0x1F19A),
+        (0x1F300, 0x1F64F),
+        (0x1F680, 0x1F6FF),
+        (0x1F7E0, 0x1F7EB),
+        (0x1F900, 0x1F9FF),
+        (0x1FA70, 0x1FAFF),
+        (0x20000, 0x3FFFD),
+    ];
+
+    let in_table = |table: &[(u32, u32)]| {
+        let c = u32::from(c);
+        table
+            .binary_search_by(|&(start, end)| {
+                if end < c {
+                    std::cmp::Ordering::Less
+                } else if start > c {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    };
+
+    if c.is_control() || in_table(ZERO) {
+        0
+    } else if in_table(WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+/// The amount of columns the text occupies in the terminal,
+/// escape sequences and control characters are not counted.
+pub(crate) fn display_width(text: &str) -> usize {
+    Tokens(text)
+        .map(|token| match token {
+            Token::Text(text) => text.chars().map(char_width).sum(),
+            Token::Escape(_) | Token::Control(_) => 0,
+        })
+        .sum()
+}
+
 pub static SHARED_LOCK: Mutex<()> = Mutex::new(());
 
 /// A convenience wrapper for `Arc<Mutex<_>>`
@@ -63,23 +408,33 @@ where
     }
 
     /// Lock this shared object and the global shared lock.
-    /// 
+    ///
     /// # Deadlocks
-    /// 
+    ///
     /// This function also locks a global lock that is
-    /// used to uphold the guarantee that frames will not
-    /// change between displaying and clearing (otherwise
-    /// groups could clear more lines than they displayed).
-    /// 
+    /// held while loops render a snapshot of their frames
+    /// along with how to clear them, so that frames will not
+    /// change in between (otherwise groups could clear more
+    /// lines than they displayed). The lock is not held while
+    /// the snapshot is written.
+    ///
     /// This means that locking even two different `Shared`
     /// objects on the same thread will lead to a deadlock.
-    #[allow(clippy::missing_panics_doc)]
-    pub fn lock(&self) -> SharedLockGuard<F> {
+    pub fn lock(&self) -> SharedLockGuard<'_, F> {
         SharedLockGuard {
-            _shared_lock: SHARED_LOCK.lock().unwrap(),
-            inner_lock: self.inner.lock().unwrap(),
+            _shared_lock: SHARED_LOCK.lock().unwrap_or_else(PoisonError::into_inner),
+            inner_lock: self.lock_inner(),
         }
     }
+
+    /// Lock only this shared object, for when the global
+    /// shared lock can already be held by the caller.
+    ///
+    /// A poisoned lock is recovered, the frames are still
+    /// displayed after a panic elsewhere.
+    pub(crate) fn lock_inner(&self) -> MutexGuard<'_, F> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 impl<F> Clone for SharedFrames<F>
@@ -98,7 +453,7 @@ where
     F: Frames,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.inner.lock().unwrap().fmt(f)
+        self.lock_inner().fmt(f)
     }
 }
 
@@ -107,19 +462,83 @@ where
     F: Frames,
 {
     fn advance(&mut self) {
-        self.inner.lock().unwrap().advance();
+        self.lock_inner().advance();
+    }
+
+    fn advance_at(&mut self, now: Instant) {
+        self.lock_inner().advance_at(now);
     }
 
     fn reset(&mut self) {
-        self.inner.lock().unwrap().reset();
+        self.lock_inner().reset();
+    }
+
+    fn render(&self, buf: &mut String) {
+        self.lock_inner().render(buf);
+    }
+
+    fn repaint(&self, buf: &mut String) {
+        self.lock_inner().repaint(buf);
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.lock_inner().is_dirty()
+    }
+
+    fn mark_clean(&mut self) {
+        self.lock_inner().mark_clean();
     }
 
     fn clear(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.inner.lock().unwrap().clear(f)
+        self.lock_inner().clear(f)
+    }
+
+    fn rewind(&self, f: &mut std::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.lock_inner().rewind(f)
     }
 
     fn lines(&self) -> usize {
-        self.inner.lock().unwrap().lines()
+        self.lock_inner().lines()
+    }
+
+    fn total_lines(&self) -> usize {
+        self.lock_inner().total_lines()
+    }
+
+    fn displayed_lines(&self) -> usize {
+        self.lock_inner().displayed_lines()
+    }
+
+    fn lines_changed(&self) -> bool {
+        self.lock_inner().lines_changed()
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        self.lock_inner().frame_count()
+    }
+
+    fn state(&self) -> crate::FrameState {
+        self.lock_inner().state()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.lock_inner().is_finished()
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        self.lock_inner().interval()
+    }
+
+    fn label(&self) -> Option<String> {
+        self.lock_inner().label()
+    }
+
+    fn for_each_child(&self, f: &mut dyn FnMut(&dyn Frames)) {
+        self.lock_inner().for_each_child(f);
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.lock_inner().progress()
     }
 }
 
@@ -130,15 +549,89 @@ pub struct SharedLockGuard<'l, F> {
     inner_lock: MutexGuard<'l, F>,
 }
 
-impl<'l, F> std::ops::Deref for SharedLockGuard<'l, F> {
+impl<F> std::ops::Deref for SharedLockGuard<'_, F> {
     type Target = F;
 
     fn deref(&self) -> &Self::Target {
-        &*self.inner_lock
+        &self.inner_lock
     }
 }
-impl<'l, F> std::ops::DerefMut for SharedLockGuard<'l, F> {
+impl<F> std::ops::DerefMut for SharedLockGuard<'_, F> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut *self.inner_lock
+        &mut self.inner_lock
+    }
+}
+
+/// A stream that discards everything written to it
+/// while counting the written bytes and flushes.
+///
+/// Clones share the same counters, which makes it useful
+/// for measuring how expensive rendering of frames is.
+///
+/// # Example
+///
+/// ```
+/// # use termspin::{spinner, Line, Loop, NullStream};
+/// # use std::time::Duration;
+/// let stream = NullStream::new();
+/// let l = Loop::new(Duration::from_millis(10), Line::new(spinner::dots()));
+///
+/// l.spawn_stream(stream.clone());
+/// std::thread::sleep(Duration::from_millis(100));
+/// l.stop();
+///
+/// println!("{} bytes in {} frames", stream.bytes(), stream.flushes());
+/// ```
+#[must_use]
+#[derive(Debug, Default, Clone)]
+pub struct NullStream {
+    counters: Arc<NullStreamCounters>,
+}
+
+#[derive(Debug, Default)]
+struct NullStreamCounters {
+    bytes: AtomicU64,
+    writes: AtomicU64,
+    flushes: AtomicU64,
+}
+
+impl NullStream {
+    /// Create a new stream with zeroed counters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The amount of bytes written.
+    #[must_use]
+    pub fn bytes(&self) -> u64 {
+        self.counters.bytes.load(Ordering::Relaxed)
+    }
+
+    /// The amount of write calls.
+    #[must_use]
+    pub fn writes(&self) -> u64 {
+        self.counters.writes.load(Ordering::Relaxed)
+    }
+
+    /// The amount of flushes, with [`FlushPolicy::EveryFrame`](crate::FlushPolicy::EveryFrame)
+    /// this is the amount of rendered frames.
+    #[must_use]
+    pub fn flushes(&self) -> u64 {
+        self.counters.flushes.load(Ordering::Relaxed)
+    }
+}
+
+impl std::io::Write for NullStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.counters
+            .bytes
+            .fetch_add(buf.len() as u64, Ordering::Relaxed);
+        self.counters.writes.fetch_add(1, Ordering::Relaxed);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.counters.flushes.fetch_add(1, Ordering::Relaxed);
+        Ok(())
     }
 }